@@ -1,5 +1,7 @@
 use proc_macro::TokenStream;
-use syn::{Attribute, DeriveInput, Meta, Type, parse_macro_input, parse_quote};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Attribute, DeriveInput, FnArg, Ident, ItemFn, Meta, Token, Type, parse_macro_input, parse_quote};
 
 pub fn derive_medi_command_inner(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -9,18 +11,74 @@ pub fn derive_medi_command_inner(input: proc_macro::TokenStream) -> proc_macro::
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     // Look for #[medi_command(return_type = SomeType)] attribute
-    let return_type = extract_return_type(&input.attrs).unwrap_or_else(|| parse_quote!(()));
+    let return_type = extract_type_arg(&input.attrs, "medi_command", "return_type").unwrap_or_else(|| parse_quote!(()));
 
+    // Only a request without generics is known to be `Clone` or not, generic requests are not copied
+    let clone_request = generics.params.is_empty().then(expand_clone_request);
     let expanded = quote::quote! {
-        impl #impl_generics IntoCommand<#return_type> for #name #ty_generics #where_clause {}
+        impl #impl_generics IntoCommand<#return_type> for #name #ty_generics #where_clause {
+            #clone_request
+        }
     };
 
     TokenStream::from(expanded)
 }
 
-fn extract_return_type(attrs: &[Attribute]) -> Option<Type> {
+/// Copy the request for another attempt of a retry policy if it is `Clone`.
+/// The method resolution prefers the impl of `CopyClone` on `Probe` to the one of `NoCopy` on `&Probe`,
+/// which is only chosen if the request does not implement `Clone`.
+fn expand_clone_request() -> proc_macro2::TokenStream {
+    quote::quote! {
+        fn clone_request(&self) -> ::core::option::Option<Self> {
+            struct Probe<'a, T>(&'a T);
+
+            trait CopyClone<T> {
+                fn copy(&self) -> ::core::option::Option<T>;
+            }
+
+            impl<T: ::core::clone::Clone> CopyClone<T> for Probe<'_, T> {
+                fn copy(&self) -> ::core::option::Option<T> {
+                    ::core::option::Option::Some(self.0.clone())
+                }
+            }
+
+            trait NoCopy<T> {
+                fn copy(&self) -> ::core::option::Option<T>;
+            }
+
+            impl<T> NoCopy<T> for &Probe<'_, T> {
+                fn copy(&self) -> ::core::option::Option<T> {
+                    ::core::option::Option::None
+                }
+            }
+
+            (&Probe(self)).copy()
+        }
+    }
+}
+
+pub fn derive_medi_stream_command_inner(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let name = &input.ident;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Look for #[medi_stream_command(item_type = SomeType)] attribute
+    let item_type =
+        extract_type_arg(&input.attrs, "medi_stream_command", "item_type").unwrap_or_else(|| parse_quote!(()));
+
+    let expanded = quote::quote! {
+        impl #impl_generics IntoStreamCommand<#item_type> for #name #ty_generics #where_clause {}
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Extract the type of a `#[attr_name(key = SomeType)]` attribute
+fn extract_type_arg(attrs: &[Attribute], attr_name: &str, arg_name: &str) -> Option<Type> {
     for attr in attrs {
-        if !attr.path().is_ident("medi_command") {
+        if !attr.path().is_ident(attr_name) {
             continue;
         }
 
@@ -32,13 +90,13 @@ fn extract_return_type(attrs: &[Attribute]) -> Option<Type> {
         let tokens = &meta_list.tokens;
         let tokens_str = tokens.to_string();
 
-        // Look for "return_type = TypeName" pattern (without quotes)
+        // Look for "key = TypeName" pattern (without quotes)
         let Some(eq_pos) = tokens_str.find('=') else {
             continue;
         };
 
         let key = tokens_str[..eq_pos].trim();
-        if key != "return_type" {
+        if key != arg_name {
             continue;
         }
 
@@ -77,3 +135,63 @@ pub fn derive_medi_ressource_inner(input: proc_macro::TokenStream) -> proc_macro
 
     TokenStream::from(expanded)
 }
+
+pub fn medi_handler_inner(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let args = parse_macro_input!(args with Punctuated::<Ident, Token![,]>::parse_terminated);
+    let input = parse_macro_input!(input as ItemFn);
+
+    match expand_medi_handler(&args, &input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+/// Register the handler function with `#[medi_handler]`, `#[medi_handler(event)]` or `#[medi_handler(stream)]`.
+/// Functions which are not async are registered as blocking handlers.
+fn expand_medi_handler(args: &Punctuated<Ident, Token![,]>, input: &ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let mut is_event = false;
+    let mut is_stream = false;
+    for arg in args {
+        match arg.to_string().as_str() {
+            "event" => is_event = true,
+            "stream" => is_stream = true,
+            _ => return Err(syn::Error::new(arg.span(), "expected `event` or `stream`")),
+        }
+    }
+    if is_event && is_stream {
+        return Err(syn::Error::new(
+            args.span(),
+            "a handler can not be an event and a stream handler",
+        ));
+    }
+
+    // The request or event is the last argument, the ones before are extracted from the resources
+    let Some(FnArg::Typed(req)) = input.sig.inputs.last() else {
+        return Err(syn::Error::new(
+            input.sig.span(),
+            "the handler requires the request or event as last argument",
+        ));
+    };
+    let req_type = &req.ty;
+
+    let is_async = input.sig.asyncness.is_some();
+    let (constructor, add_handler) = match (is_event, is_stream, is_async) {
+        (true, _, true) => (quote::quote!(event), quote::quote!(add_event_handler)),
+        (true, _, false) => (quote::quote!(event), quote::quote!(add_blocking_event_handler)),
+        (_, true, _) => (quote::quote!(stream), quote::quote!(add_stream_handler)),
+        (_, _, true) => (quote::quote!(request), quote::quote!(add_req_handler)),
+        (_, _, false) => (quote::quote!(request), quote::quote!(add_blocking_req_handler)),
+    };
+
+    let name = &input.sig.ident;
+    Ok(quote::quote! {
+        #input
+
+        ::medi_rs::inventory::submit! {
+            ::medi_rs::DeclaredHandler::#constructor::<#req_type>(
+                concat!(module_path!(), "::", stringify!(#name)),
+                |builder| builder.#add_handler(#name),
+            )
+        }
+    })
+}
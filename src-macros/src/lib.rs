@@ -1,12 +1,20 @@
 mod functions;
 
-use functions::{derive_medi_command_inner, derive_medi_event_inner, derive_medi_ressource_inner};
+use functions::{
+    derive_medi_command_inner, derive_medi_event_inner, derive_medi_ressource_inner, derive_medi_stream_command_inner,
+    medi_handler_inner,
+};
 
 #[proc_macro_derive(MediCommand, attributes(medi_command))]
 pub fn derive_medi_command(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_medi_command_inner(input)
 }
 
+#[proc_macro_derive(MediStreamCommand, attributes(medi_stream_command))]
+pub fn derive_medi_stream_command(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_medi_stream_command_inner(input)
+}
+
 #[proc_macro_derive(MediEvent)]
 pub fn derive_medi_event(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_medi_event_inner(input)
@@ -16,3 +24,11 @@ pub fn derive_medi_event(input: proc_macro::TokenStream) -> proc_macro::TokenStr
 pub fn derive_medi_ressource(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_medi_ressource_inner(input)
 }
+
+/// Declare a handler function which is added to the bus with `BusBuilder::add_registered_handlers`.
+/// Use `#[medi_handler(event)]` for event handlers and `#[medi_handler(stream)]` for stream handlers.
+/// The handlers use the global configuration of the builder, e.g. `BusBuilder::retry_policy`.
+#[proc_macro_attribute]
+pub fn medi_handler(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    medi_handler_inner(args, input)
+}
@@ -0,0 +1,62 @@
+mod pipeline;
+mod request;
+
+// -- flatten
+pub(crate) use pipeline::Pipelines;
+pub use request::*;
+
+use crate::handler_wrapper::HandlerWrapperTrait;
+use crate::{Error, Resources, Result};
+use std::{pin::Pin, sync::Arc};
+
+/// Future returned by a behavior and by the `next` continuation
+pub type BehaviorFuture<'a> = Pin<Box<dyn futures::Future<Output = Result<Response>> + Send + 'a>>;
+
+/// Behavior trait will be used to run cross-cutting logic around the dispatch of a request.
+///
+/// A behavior receives the type erased request and the `next` continuation.
+/// Calling `next.run(req)` runs the remaining behaviors and finally the handler,
+/// not calling it short-circuits the pipeline with the returned result.
+pub trait Behavior: Send + Sync + 'static {
+    fn handle<'a>(&'a self, req: Request, next: Next<'a>) -> BehaviorFuture<'a>;
+}
+
+/// Continuation to the remaining behaviors and the request handler
+pub struct Next<'a> {
+    behaviors: &'a [Arc<dyn Behavior>],
+    handler: Option<&'a Arc<dyn HandlerWrapperTrait>>,
+    resources: &'a Resources,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(
+        behaviors: &'a [Arc<dyn Behavior>],
+        handler: Option<&'a Arc<dyn HandlerWrapperTrait>>,
+        resources: &'a Resources,
+    ) -> Self {
+        Next {
+            behaviors,
+            handler,
+            resources,
+        }
+    }
+
+    /// Run the next behavior in the pipeline or the handler if no behavior is left
+    pub fn run(self, req: Request) -> BehaviorFuture<'a> {
+        if let Some((behavior, behaviors)) = self.behaviors.split_first() {
+            let next = Next {
+                behaviors,
+                handler: self.handler,
+                resources: self.resources,
+            };
+            return behavior.handle(req, next);
+        }
+
+        let Some(handler) = self.handler else {
+            let request = req.type_name();
+            return Box::pin(async move { Err(Error::HandlerNotFound { request }) });
+        };
+
+        handler.handle(self.resources.clone(), req.into_inner())
+    }
+}
@@ -0,0 +1,30 @@
+use std::{any::TypeId, collections::HashMap, sync::Arc};
+
+use super::Behavior;
+
+/// Behaviors resolved per request type.
+/// The global behaviors always come first, followed by the request specific ones,
+/// both in the order of their registration.
+#[derive(Clone, Default)]
+pub(crate) struct Pipelines {
+    global: Vec<Arc<dyn Behavior>>,
+    per_request: HashMap<TypeId, Vec<Arc<dyn Behavior>>>,
+}
+
+impl Pipelines {
+    pub(crate) fn new(global: Vec<Arc<dyn Behavior>>, per_request: HashMap<TypeId, Vec<Arc<dyn Behavior>>>) -> Self {
+        let per_request = per_request
+            .into_iter()
+            .map(|(type_id, behaviors)| {
+                let pipeline = global.iter().cloned().chain(behaviors).collect();
+                (type_id, pipeline)
+            })
+            .collect();
+
+        Pipelines { global, per_request }
+    }
+
+    pub(crate) fn for_request(&self, type_id: &TypeId) -> &[Arc<dyn Behavior>] {
+        self.per_request.get(type_id).unwrap_or(&self.global)
+    }
+}
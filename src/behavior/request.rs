@@ -0,0 +1,48 @@
+use std::any::{Any, TypeId};
+
+/// Type erased response of a request handler
+pub type Response = Box<dyn Any + Send + Sync>;
+
+/// Type erased request which is passed through the behaviors
+pub struct Request {
+    type_id: TypeId,
+    type_name: &'static str,
+    value: Box<dyn Any + Send + Sync>,
+}
+
+impl Request {
+    pub(crate) fn new<Req>(value: Req) -> Self
+    where
+        Req: Send + Sync + 'static,
+    {
+        Request {
+            type_id: TypeId::of::<Req>(),
+            type_name: std::any::type_name::<Req>(),
+            value: Box::new(value),
+        }
+    }
+
+    /// Type id of the request
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Type name of the request, e.g. for logging
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Get a reference to the request if it is of type `T`
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.value.downcast_ref::<T>()
+    }
+
+    /// Get a mutable reference to the request if it is of type `T`
+    pub fn downcast_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.value.downcast_mut::<T>()
+    }
+
+    pub(crate) fn into_inner(self) -> Box<dyn Any + Send + Sync> {
+        self.value
+    }
+}
@@ -1,52 +1,376 @@
+use crate::{Behavior, DeadLetterQueue, DeadLetterStore, EventMiddleware, Pipelines};
+use crate::{BlockingHandler, BlockingHandlerWrapperTrait, SpawnBlockingHandler};
+use crate::{CloneValueFn, HandlerConfig, RetryHandler, RetryPolicy, TimeoutHandler, clone_request, clone_value};
+use crate::{Error, ResourceType, Resources, Result, Runtime, Spawner, default_spawner};
 use crate::{FromResources, Handler, IntoCommand, IntoEvent, SharedHandler, handler_wrapper::HandlerWrapperTrait};
-use crate::{Resources, Result};
+use crate::{IntoStreamCommand, StreamHandler, StreamHandlerWrapperTrait};
 use std::any::TypeId;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
-use super::Bus;
+use super::{Bus, EventLoopFuture, EventLoopMode};
+
+/// Kind of a registered handler
+enum RegisteredHandler {
+    Async(Arc<dyn HandlerWrapperTrait>),
+    /// Executed with `Spawner::spawn_blocking`
+    Blocking(Arc<dyn BlockingHandlerWrapperTrait>),
+}
+
+/// Registered handler with its configuration until the bus is built
+struct Registration {
+    handler: RegisteredHandler,
+    config: HandlerConfig,
+    /// Copies the request or event for another attempt, the handler is not retried if it returns `None`
+    clone_value: CloneValueFn,
+}
+
+impl Registration {
+    fn name(&self) -> &'static str {
+        match &self.handler {
+            RegisteredHandler::Async(handler) => handler.name(),
+            RegisteredHandler::Blocking(handler) => handler.name(),
+        }
+    }
+
+    fn into_handler(self, defaults: &HandlerConfig, runtime: &Runtime) -> Arc<dyn HandlerWrapperTrait> {
+        let config = self.config.or(defaults);
+
+        let mut handler = match self.handler {
+            RegisteredHandler::Async(handler) => handler,
+            RegisteredHandler::Blocking(handler) => Arc::new(SpawnBlockingHandler::new(handler, runtime.clone())),
+        };
+        if let Some(timeout) = config.timeout {
+            handler = Arc::new(TimeoutHandler::new(handler, timeout, runtime.clone()));
+        }
+        if let Some(policy) = config.retry {
+            handler = Arc::new(RetryHandler::new(handler, policy, self.clone_value, runtime.clone()));
+        }
+
+        handler
+    }
+}
+
+/// Request type of a handler which has to be unique per bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UniqueHandler {
+    Request(TypeId),
+    Stream(TypeId),
+}
+
+/// Resources which a handler extracts, validated when the bus is built
+struct HandlerDependencies {
+    handler: &'static str,
+    request: &'static str,
+    /// Only set for request and stream handlers, which can be replaced
+    unique: Option<UniqueHandler>,
+    resources: Vec<ResourceType>,
+}
+
+impl HandlerDependencies {
+    fn of<H, Req>(resources: Vec<ResourceType>) -> Self {
+        HandlerDependencies {
+            handler: std::any::type_name::<H>(),
+            request: std::any::type_name::<Req>(),
+            unique: None,
+            resources,
+        }
+    }
+}
 
 #[derive(Default)]
 pub struct BusBuilder {
-    req_handlers: SharedHandler<Arc<dyn HandlerWrapperTrait>>,
-    evt_handlers: SharedHandler<Vec<Arc<dyn HandlerWrapperTrait>>>,
+    req_handlers: SharedHandler<Registration>,
+    evt_handlers: SharedHandler<Vec<Registration>>,
+    stream_handlers: SharedHandler<Arc<dyn StreamHandlerWrapperTrait>>,
+    defaults: HandlerConfig,
     resources: Resources,
+    behaviors: Vec<Arc<dyn Behavior>>,
+    req_behaviors: HashMap<TypeId, Vec<Arc<dyn Behavior>>>,
+    evt_middlewares: Vec<Arc<dyn EventMiddleware>>,
+    dead_letters: DeadLetterQueue,
+    event_loop_mode: EventLoopMode,
+    spawner: Option<Arc<dyn Spawner>>,
+    dependencies: Vec<HandlerDependencies>,
+    allow_override: bool,
+    /// Configuration errors which are returned by `build`
+    errors: Vec<Error>,
+    #[cfg(feature = "testing")]
+    recorded_events: Option<super::RecordedEvents>,
 }
 
 impl BusBuilder {
-    pub fn add_req_handler<H, T, Req, Res>(mut self, h: H) -> Self
+    /// Add a handler for requests of type `Req`.
+    /// Another handler for the same request type is reported as `Error::DuplicateHandler` by `build`,
+    /// unless `allow_handler_override` is set. The global retry policy applies if `IntoCommand::clone_request`
+    /// copies the request, which `#[derive(MediCommand)]` does for `Clone` requests.
+    pub fn add_req_handler<H, T, Req, Res>(self, h: H) -> Self
     where
         H: Handler<T, Req, Res> + Sync + Send + 'static,
         T: Sync + Send + 'static,
         Req: IntoCommand<Res> + Sync + Send + 'static,
         Res: Sync + Send + 'static,
+    {
+        let handler = RegisteredHandler::Async(h.into_dyn());
+        self.register_req_handler::<Req>(
+            handler,
+            HandlerConfig::default(),
+            clone_request::<Req, Res>,
+            H::resource_types(),
+        )
+    }
+
+    /// Add a request handler with its own configuration.
+    /// Retrying needs a copy of the request for each attempt, therefore the request has to be `Clone`.
+    pub fn add_req_handler_with<H, T, Req, Res>(self, h: H, config: HandlerConfig) -> Self
+    where
+        H: Handler<T, Req, Res> + Sync + Send + 'static,
+        T: Sync + Send + 'static,
+        Req: IntoCommand<Res> + Clone + Sync + Send + 'static,
+        Res: Sync + Send + 'static,
+    {
+        let handler = RegisteredHandler::Async(h.into_dyn());
+        self.register_req_handler::<Req>(handler, config, clone_value::<Req>, H::resource_types())
+    }
+
+    /// Add a synchronous request handler which is executed on the blocking thread pool of the runtime.
+    /// A timeout does not stop the blocking function, the bus only stops waiting for it.
+    pub fn add_blocking_req_handler<H, T, Req, Res>(self, h: H) -> Self
+    where
+        H: BlockingHandler<T, Req, Res> + Sync + Send + 'static,
+        T: Sync + Send + 'static,
+        Req: IntoCommand<Res> + Sync + Send + 'static,
+        Res: Sync + Send + 'static,
+    {
+        let handler = RegisteredHandler::Blocking(h.into_dyn());
+        self.register_req_handler::<Req>(
+            handler,
+            HandlerConfig::default(),
+            clone_request::<Req, Res>,
+            H::resource_types(),
+        )
+    }
+
+    /// Add a synchronous request handler with its own configuration, the request has to be `Clone` to be retried
+    pub fn add_blocking_req_handler_with<H, T, Req, Res>(self, h: H, config: HandlerConfig) -> Self
+    where
+        H: BlockingHandler<T, Req, Res> + Sync + Send + 'static,
+        T: Sync + Send + 'static,
+        Req: IntoCommand<Res> + Clone + Sync + Send + 'static,
+        Res: Sync + Send + 'static,
+    {
+        let handler = RegisteredHandler::Blocking(h.into_dyn());
+        self.register_req_handler::<Req>(handler, config, clone_value::<Req>, H::resource_types())
+    }
+
+    /// Add a request handler, fails with `Error::DuplicateHandler` if a handler for the request type exists
+    /// and `allow_handler_override` is not set. The builder keeps everything registered so far in both cases.
+    pub fn try_add_req_handler<H, T, Req, Res>(&mut self, h: H) -> Result<()>
+    where
+        H: Handler<T, Req, Res> + Sync + Send + 'static,
+        T: Sync + Send + 'static,
+        Req: IntoCommand<Res> + Sync + Send + 'static,
+        Res: Sync + Send + 'static,
+    {
+        self.try_add(|builder| builder.add_req_handler(h))
+    }
+
+    /// Add a blocking request handler, fails like `try_add_req_handler` if a handler for the request type exists
+    pub fn try_add_blocking_req_handler<H, T, Req, Res>(&mut self, h: H) -> Result<()>
+    where
+        H: BlockingHandler<T, Req, Res> + Sync + Send + 'static,
+        T: Sync + Send + 'static,
+        Req: IntoCommand<Res> + Sync + Send + 'static,
+        Res: Sync + Send + 'static,
+    {
+        self.try_add(|builder| builder.add_blocking_req_handler(h))
+    }
+
+    /// Add a stream handler, fails like `try_add_req_handler` if a stream handler for the request type exists
+    pub fn try_add_stream_handler<H, T, Req, Item>(&mut self, h: H) -> Result<()>
+    where
+        H: StreamHandler<T, Req, Item> + Sync + Send + 'static,
+        T: Sync + Send + 'static,
+        Req: IntoStreamCommand<Item> + Sync + Send + 'static,
+        Item: Send + 'static,
+    {
+        self.try_add(|builder| builder.add_stream_handler(h))
+    }
+
+    /// Run the registration and return its configuration error instead of keeping it for `build`
+    fn try_add(&mut self, add: impl FnOnce(Self) -> Self) -> Result<()> {
+        let errors = self.errors.len();
+        *self = add(std::mem::take(self));
+        if self.errors.len() > errors {
+            return Err(self.errors.remove(errors));
+        }
+
+        Ok(())
+    }
+
+    /// Replace existing request and stream handlers when another handler is added for the same request type,
+    /// e.g. to replace a handler with a stub in tests. By default a duplicate handler is a configuration error.
+    pub fn allow_handler_override(mut self) -> Self {
+        self.allow_override = true;
+        self
+    }
+
+    fn register_req_handler<Req>(
+        mut self,
+        handler: RegisteredHandler,
+        config: HandlerConfig,
+        clone_value: CloneValueFn,
+        resources: Vec<ResourceType>,
+    ) -> Self
+    where
+        Req: 'static,
     {
         let type_id = TypeId::of::<Req>();
+        let registration = Registration {
+            handler,
+            config,
+            clone_value,
+        };
 
-        if self.req_handlers.contains_key(&type_id) {
-            let type_name = std::any::type_name::<Req>();
-            panic!("Handler already exists for type: {}", type_name);
+        let key = UniqueHandler::Request(type_id);
+        let existing = self.req_handlers.get(&type_id).map(Registration::name);
+        if self.is_duplicate::<Req>(existing, registration.name()) {
+            return self;
         }
 
-        self.req_handlers.insert(type_id, h.into_dyn());
+        self.replace_dependencies::<Req>(key, registration.name(), resources);
+        self.req_handlers.insert(type_id, registration);
 
         self
     }
 
-    pub fn add_event_handler<H, T, Evt>(mut self, h: H) -> Self
+    /// Add a handler which returns a stream of items for requests of type `Req`
+    pub fn add_stream_handler<H, T, Req, Item>(mut self, h: H) -> Self
+    where
+        H: StreamHandler<T, Req, Item> + Sync + Send + 'static,
+        T: Sync + Send + 'static,
+        Req: IntoStreamCommand<Item> + Sync + Send + 'static,
+        Item: Send + 'static,
+    {
+        let type_id = TypeId::of::<Req>();
+        let handler = h.into_dyn();
+
+        let key = UniqueHandler::Stream(type_id);
+        let existing = self.stream_handlers.get(&type_id).map(|handler| handler.name());
+        if self.is_duplicate::<Req>(existing, handler.name()) {
+            return self;
+        }
+
+        self.replace_dependencies::<Req>(key, handler.name(), H::resource_types());
+        self.stream_handlers.insert(type_id, handler);
+
+        self
+    }
+
+    /// Record an `Error::DuplicateHandler` if a handler exists and may not be replaced
+    fn is_duplicate<Req>(&mut self, existing: Option<&'static str>, handler: &'static str) -> bool {
+        let Some(existing) = existing else {
+            return false;
+        };
+        if self.allow_override {
+            return false;
+        }
+
+        self.errors.push(Error::DuplicateHandler {
+            request: std::any::type_name::<Req>(),
+            handlers: vec![existing, handler],
+        });
+        true
+    }
+
+    /// Set the dependencies of the handler, the ones of a replaced handler are not validated anymore
+    fn replace_dependencies<Req>(&mut self, key: UniqueHandler, handler: &'static str, resources: Vec<ResourceType>) {
+        self.dependencies
+            .retain(|dependencies| dependencies.unique != Some(key));
+        self.dependencies.push(HandlerDependencies {
+            handler,
+            request: std::any::type_name::<Req>(),
+            unique: Some(key),
+            resources,
+        });
+    }
+
+    pub fn add_event_handler<H, T, Evt>(self, h: H) -> Self
     where
         H: Handler<T, Evt, ()> + Sync + Send + 'static,
         T: Sync + Send + 'static,
-        Evt: IntoEvent + Sync + Send + 'static,
+        Evt: IntoEvent + Clone + Sync + Send + 'static,
+    {
+        self.add_event_handler_with(h, HandlerConfig::default())
+    }
+
+    /// Add an event handler with its own configuration
+    pub fn add_event_handler_with<H, T, Evt>(mut self, h: H, config: HandlerConfig) -> Self
+    where
+        H: Handler<T, Evt, ()> + Sync + Send + 'static,
+        T: Sync + Send + 'static,
+        Evt: IntoEvent + Clone + Sync + Send + 'static,
     {
         let type_id = TypeId::of::<Evt>();
+        self.dependencies
+            .push(HandlerDependencies::of::<H, Evt>(H::resource_types()));
 
-        self.evt_handlers.entry(type_id).or_default();
-        let handlers = self.evt_handlers.get_mut(&type_id).unwrap();
-        handlers.push(h.into_dyn());
+        let registration = Registration {
+            handler: RegisteredHandler::Async(h.into_dyn()),
+            config,
+            clone_value: clone_value::<Evt>,
+        };
+        self.evt_handlers.entry(type_id).or_default().push(registration);
 
         self
     }
 
+    /// Add a synchronous event handler which is executed on the blocking thread pool of the runtime
+    pub fn add_blocking_event_handler<H, T, Evt>(self, h: H) -> Self
+    where
+        H: BlockingHandler<T, Evt, ()> + Sync + Send + 'static,
+        T: Sync + Send + 'static,
+        Evt: IntoEvent + Clone + Sync + Send + 'static,
+    {
+        self.add_blocking_event_handler_with(h, HandlerConfig::default())
+    }
+
+    /// Add a synchronous event handler with its own configuration
+    pub fn add_blocking_event_handler_with<H, T, Evt>(mut self, h: H, config: HandlerConfig) -> Self
+    where
+        H: BlockingHandler<T, Evt, ()> + Sync + Send + 'static,
+        T: Sync + Send + 'static,
+        Evt: IntoEvent + Clone + Sync + Send + 'static,
+    {
+        let type_id = TypeId::of::<Evt>();
+        self.dependencies
+            .push(HandlerDependencies::of::<H, Evt>(H::resource_types()));
+
+        let registration = Registration {
+            handler: RegisteredHandler::Blocking(h.into_dyn()),
+            config,
+            clone_value: clone_value::<Evt>,
+        };
+        self.evt_handlers.entry(type_id).or_default().push(registration);
+
+        self
+    }
+
+    /// Set the retry policy for all handlers without their own policy.
+    /// Retrying needs a copy of the request for each attempt, requests which are not copied by
+    /// `IntoCommand::clone_request`, e.g. because they are not `Clone`, are handled once.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.defaults.retry = Some(policy);
+        self
+    }
+
+    /// Set the timeout for all handlers without their own timeout
+    pub fn handler_timeout(mut self, timeout: Duration) -> Self {
+        self.defaults.timeout = Some(timeout);
+        self
+    }
+
     pub fn append_resources<T>(mut self, value: T) -> Self
     where
         T: FromResources + Clone + Send + Sync + 'static,
@@ -55,8 +379,175 @@ impl BusBuilder {
         self
     }
 
-    pub fn build(self) -> Result<Bus> {
-        let bus = Bus::new(self.resources, self.req_handlers, self.evt_handlers);
+    /// Add a behavior which runs around the dispatch of every request.
+    /// Global behaviors run in the order of their registration and before any request specific behavior.
+    pub fn add_behavior<B>(mut self, behavior: B) -> Self
+    where
+        B: Behavior,
+    {
+        self.behaviors.push(Arc::new(behavior));
+        self
+    }
+
+    /// Add a behavior which only runs around the dispatch of requests of type `Req`.
+    /// Request specific behaviors run in the order of their registration and after all global behaviors.
+    pub fn add_req_behavior<Req, B>(mut self, behavior: B) -> Self
+    where
+        Req: Send + Sync + 'static,
+        B: Behavior,
+    {
+        let type_id = TypeId::of::<Req>();
+        self.req_behaviors.entry(type_id).or_default().push(Arc::new(behavior));
+        self
+    }
+
+    /// Add a middleware which runs around the fan-out of every event to its handlers.
+    /// Middlewares run in the order of their registration.
+    pub fn add_event_middleware<M>(mut self, middleware: M) -> Self
+    where
+        M: EventMiddleware,
+    {
+        self.evt_middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Set the store which keeps the events of failed event handlers, by default they are kept in memory
+    pub fn dead_letter_store<S>(mut self, store: S) -> Self
+    where
+        S: DeadLetterStore,
+    {
+        self.dead_letters = DeadLetterQueue::new(Arc::new(store));
+        self
+    }
+
+    /// Queue the published events until `Bus::run_until_idle` is called instead of processing them in the background.
+    /// Useful for tests whose outcome should not depend on the scheduling of the event loop.
+    pub fn manual_event_processing(mut self) -> Self {
+        self.event_loop_mode = EventLoopMode::Manual;
+        self
+    }
+
+    /// Record the published events instead of processing them, used by the `RecordingBus`
+    #[cfg(feature = "testing")]
+    pub(crate) fn record_events(mut self, recorded_events: super::RecordedEvents) -> Self {
+        self.recorded_events = Some(recorded_events);
+        self
+    }
+
+    /// Set the spawner of the async runtime which runs the event loop and the event handlers.
+    /// By default the spawner of the enabled runtime feature is used, tokio is preferred over smol and async-std.
+    pub fn spawner<S>(mut self, spawner: S) -> Self
+    where
+        S: Spawner,
+    {
+        self.spawner = Some(Arc::new(spawner));
+        self
+    }
+
+    /// Build the bus without spawning its event loop, e.g. before the async runtime is started.
+    /// The returned event loop future has to be driven by the caller, on its own task or executor,
+    /// until then the published events are queued. Event handlers are still spawned with the spawner.
+    pub fn build_with_event_loop(mut self) -> Result<(Bus, EventLoopFuture)> {
+        self.event_loop_mode = EventLoopMode::External;
+        let bus = self.build()?;
+        let event_loop = bus.event_loop_future();
+        Ok((bus, event_loop))
+    }
+
+    /// Add all handlers which are declared with `#[medi_handler]` in the linked crates.
+    /// Several request or stream handlers for the same request type, including one which was added before,
+    /// are reported as `Error::DuplicateHandler` by `build`, see `allow_handler_override`.
+    #[cfg(feature = "medi-rs-macros")]
+    pub fn add_registered_handlers(mut self) -> Self {
+        let mut unique_handlers: HashMap<_, Vec<&crate::DeclaredHandler>> = HashMap::new();
+        for handler in crate::declared_handlers() {
+            match handler.kind {
+                crate::DeclaredHandlerKind::Event => self = (handler.register)(self),
+                kind => unique_handlers
+                    .entry((kind, (handler.message_type)()))
+                    .or_default()
+                    .push(handler),
+            }
+        }
+
+        // the order of the declared handlers is not defined, therefore none of them is preferred
+        for handlers in unique_handlers.into_values() {
+            match handlers.as_slice() {
+                [handler] => self = (handler.register)(self),
+                _ => self.errors.push(Error::DuplicateHandler {
+                    request: (handlers[0].message_name)(),
+                    handlers: handlers.iter().map(|handler| handler.name).collect(),
+                }),
+            }
+        }
+
+        self
+    }
+
+    /// Report each resource which is extracted by a handler but not appended to the bus.
+    /// The bus itself is appended when it is built.
+    fn validate_resources(&mut self) {
+        for dependencies in &self.dependencies {
+            let missing = dependencies.resources.iter().filter(|resource| {
+                resource.type_id != TypeId::of::<Bus>() && !self.resources.contains(resource.type_id)
+            });
+
+            for resource in missing {
+                self.errors.push(Error::MissingResource {
+                    handler: dependencies.handler,
+                    request: dependencies.request,
+                    resource: resource.name(),
+                });
+            }
+        }
+    }
+
+    /// Build the bus, fails with `Error::InvalidConfiguration` if the handlers are misconfigured,
+    /// e.g. a resource which a handler extracts is not appended
+    pub fn build(mut self) -> Result<Bus> {
+        self.validate_resources();
+        if !self.errors.is_empty() {
+            return Err(Error::InvalidConfiguration(self.errors));
+        }
+
+        let spawner = self
+            .spawner
+            .or_else(default_spawner)
+            .ok_or(Error::NoSpawnerConfigured)?;
+        let runtime = Runtime::new(spawner);
+
+        let defaults = self.defaults;
+        let req_handlers = self
+            .req_handlers
+            .into_iter()
+            .map(|(type_id, registration)| (type_id, registration.into_handler(&defaults, &runtime)))
+            .collect();
+        let evt_handlers = self
+            .evt_handlers
+            .into_iter()
+            .map(|(type_id, registrations)| {
+                let handlers = registrations
+                    .into_iter()
+                    .map(|registration| registration.into_handler(&defaults, &runtime))
+                    .collect();
+                (type_id, handlers)
+            })
+            .collect();
+
+        let pipelines = Pipelines::new(self.behaviors, self.req_behaviors);
+        let bus = Bus::new(
+            self.resources,
+            req_handlers,
+            evt_handlers,
+            self.stream_handlers,
+            pipelines,
+            self.evt_middlewares,
+            self.dead_letters,
+            self.event_loop_mode,
+            runtime,
+            #[cfg(feature = "testing")]
+            self.recorded_events,
+        );
 
         Ok(bus)
     }
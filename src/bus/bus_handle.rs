@@ -0,0 +1,43 @@
+use super::Bus;
+use crate::error::{Error, Result};
+use crate::{IntoCommand, IntoEvent};
+
+/// Handle to call the bus from synchronous code, e.g. CLI commands, FFI callbacks or sync trait impls.
+/// The handle can be cloned into other threads, the requests are dispatched into the runtime of the bus.
+///
+/// The methods block the current thread until the bus responds, they must not be called from an async task.
+#[derive(Clone)]
+pub struct BusHandle {
+    bus: Bus,
+}
+
+impl BusHandle {
+    pub(crate) fn new(bus: Bus) -> Self {
+        BusHandle { bus }
+    }
+
+    /// Send the request to its handler on the runtime of the bus and block until the response is available.
+    /// Returns the same result as `Bus::send`, a panicking handler results in `Error::HandlerPanicked`.
+    pub fn send_blocking<Req, Res>(&self, req: Req) -> Result<Res>
+    where
+        Req: IntoCommand<Res> + Send + Sync + 'static,
+        Res: Send + Sync + 'static,
+    {
+        let bus = self.bus.clone();
+        let mut task = self.bus.runtime.spawn(async move { bus.send(req).await });
+
+        match futures::executor::block_on(task.join()) {
+            Some(Ok(res)) => res,
+            Some(Err(_)) => Err(Error::HandlerPanicked),
+            None => Err(Error::RuntimeUnavailable),
+        }
+    }
+
+    /// Publish the event and block until it is queued, see `Bus::publish`
+    pub fn publish_blocking<Evt>(&self, evt: Evt) -> Result<()>
+    where
+        Evt: IntoEvent + Clone + Send + Sync + 'static,
+    {
+        futures::executor::block_on(self.bus.publish(evt))
+    }
+}
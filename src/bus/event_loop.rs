@@ -0,0 +1,203 @@
+use std::future::Future;
+use std::pin::{Pin, pin};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_channel::{Receiver, Sender};
+use futures::future::Either;
+
+use super::{Bus, EventQueueItem};
+use crate::diagnostics::report_error;
+use crate::{TaskHandle, task};
+
+/// Report of the events which were left unprocessed when the event loop stopped
+#[derive(Debug, Default)]
+pub struct ShutdownReport {
+    /// Type names of the events which were not processed by their handlers
+    pub unprocessed_events: Vec<&'static str>,
+    /// The deadline passed before the event loop stopped, the in-flight event handlers were cancelled
+    pub deadline_exceeded: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum StopMode {
+    /// Finish the in-flight event and leave the queued events unprocessed
+    Shutdown,
+    /// Process all queued events before stopping
+    Drain,
+}
+
+/// How the published events are processed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum EventLoopMode {
+    /// A background task processes the events as they come in
+    #[default]
+    Background,
+    /// The events are queued until `Bus::run_until_idle` is called
+    Manual,
+    /// The caller drives the event loop future returned by `BusBuilder::build_with_event_loop`
+    External,
+}
+
+/// Future of the event loop which has to be driven by the caller, see `BusBuilder::build_with_event_loop`.
+/// It completes when the bus is shut down or drained.
+pub type EventLoopFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Shared state between the bus and its event loop task
+pub(crate) struct EventLoop {
+    mode: EventLoopMode,
+    accepting: AtomicBool,
+    stop_tx: Sender<StopMode>,
+    stop_rx: Receiver<StopMode>,
+    receiver: Receiver<EventQueueItem>,
+    in_flight: Mutex<Option<&'static str>>,
+    task: Mutex<Option<TaskHandle<()>>>,
+}
+
+impl EventLoop {
+    pub(crate) fn new(rx: Receiver<EventQueueItem>, mode: EventLoopMode) -> Self {
+        let (stop_tx, stop_rx) = async_channel::unbounded();
+        EventLoop {
+            mode,
+            accepting: AtomicBool::new(true),
+            stop_tx,
+            stop_rx,
+            receiver: rx,
+            in_flight: Mutex::new(None),
+            task: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn is_accepting(&self) -> bool {
+        self.accepting.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn start(self: &Arc<Self>, bus: Bus) {
+        if self.mode != EventLoopMode::Background {
+            return;
+        }
+
+        let event_loop = self.clone();
+        let runtime = bus.runtime.clone();
+        let task = runtime.spawn(async move {
+            process_event_loop(bus, event_loop).await;
+        });
+        *self.task.lock().unwrap() = Some(task);
+    }
+
+    /// Create the event loop future for the caller to drive, the bus waits for it on shutdown like for the task
+    pub(crate) fn external(self: &Arc<Self>, bus: Bus) -> EventLoopFuture {
+        let event_loop = self.clone();
+        let (task, handle) = task(async move {
+            process_event_loop(bus, event_loop).await;
+        });
+        *self.task.lock().unwrap() = Some(handle);
+        Box::pin(task)
+    }
+
+    /// Process the queued events inline until the queue is empty, returns the number of processed events.
+    /// Does nothing if the events are processed by the background task.
+    pub(crate) async fn run_until_idle(&self, bus: &Bus) -> usize {
+        if self.mode != EventLoopMode::Manual {
+            return 0;
+        }
+
+        let mut processed = 0;
+        while let Ok(event_item) = self.receiver.try_recv() {
+            process_event(bus, self, event_item).await;
+            processed += 1;
+        }
+
+        processed
+    }
+
+    /// Stop accepting new events and wait until the event loop stopped or the deadline passed
+    pub(crate) async fn stop(&self, bus: &Bus, mode: StopMode, deadline: Option<Duration>) -> ShutdownReport {
+        self.accepting.store(false, Ordering::SeqCst);
+        let _ = self.stop_tx.try_send(mode);
+
+        let mut report = ShutdownReport::default();
+        if self.mode == EventLoopMode::Manual && mode == StopMode::Drain {
+            let finished = match deadline {
+                Some(deadline) => bus.runtime.timeout(deadline, self.run_until_idle(bus)).await.is_some(),
+                None => {
+                    self.run_until_idle(bus).await;
+                    true
+                }
+            };
+
+            if !finished {
+                report.deadline_exceeded = true;
+                if let Some(type_name) = self.in_flight.lock().unwrap().take() {
+                    report.unprocessed_events.push(type_name);
+                }
+            }
+        }
+
+        let task = self.task.lock().unwrap().take();
+        if let Some(mut task) = task {
+            let finished = match deadline {
+                Some(deadline) => bus.runtime.timeout(deadline, task.join()).await.is_some(),
+                None => {
+                    task.join().await;
+                    true
+                }
+            };
+
+            if !finished {
+                // dropping the handle cancels the event loop with its in-flight event handlers
+                drop(task);
+                report.deadline_exceeded = true;
+                if let Some(type_name) = self.in_flight.lock().unwrap().take() {
+                    report.unprocessed_events.push(type_name);
+                }
+            }
+        }
+
+        self.receiver.close();
+        while let Ok(event_item) = self.receiver.try_recv() {
+            report.unprocessed_events.push(event_item.type_name());
+        }
+
+        report
+    }
+}
+
+/// Processes the event loop, handling events as they come in until the loop is stopped.
+async fn process_event_loop(bus: Bus, event_loop: Arc<EventLoop>) {
+    let rx = &event_loop.receiver;
+
+    loop {
+        // the stop signal is checked first, a shutdown leaves the queued events unprocessed
+        let stop = pin!(event_loop.stop_rx.recv());
+        let next = pin!(rx.recv());
+        let event_item = match futures::future::select(stop, next).await {
+            Either::Left((Ok(StopMode::Drain), _)) => {
+                rx.close();
+                continue;
+            }
+            Either::Left(_) => break,
+            Either::Right((Ok(event_item), _)) => event_item,
+            Either::Right((Err(_), _)) => break,
+        };
+
+        process_event(&bus, &event_loop, event_item).await;
+    }
+}
+
+/// Dispatches a single event and keeps the events of the failed handlers as dead letters
+async fn process_event(bus: &Bus, event_loop: &EventLoop, event_item: EventQueueItem) {
+    *event_loop.in_flight.lock().unwrap() = Some(event_item.type_name());
+    let outcomes = bus.dispatch(event_item).await;
+    for outcome in outcomes {
+        let Err(e) = outcome.result else {
+            continue;
+        };
+        report_error!("Error: {:?}", e);
+        if let Some(delivery) = outcome.delivery {
+            bus.dead_letters.push(delivery, outcome.handler, e, 1);
+        }
+    }
+    *event_loop.in_flight.lock().unwrap() = None;
+}
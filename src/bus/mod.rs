@@ -1,57 +1,109 @@
 mod bus_builder;
+mod bus_handle;
+mod event_loop;
 
 // -- flatten
+use async_channel::Sender;
 pub use bus_builder::BusBuilder;
+pub use bus_handle::BusHandle;
+pub(crate) use event_loop::EventLoopMode;
+use event_loop::{EventLoop, StopMode};
+pub use event_loop::{EventLoopFuture, ShutdownReport};
 use medi_rs_macros::MediRessource;
-use tokio::sync::mpsc::{self, Receiver, Sender};
 
 // -- use dependencies
-use crate::error::{Error, Result};
+use crate::error::{Error, HandlerFailure, Result};
 use crate::event::{EventWrapper, EventWrapperTrait};
 use crate::handler_wrapper::HandlerWrapperTrait;
-use crate::{FromResources, IntoCommand, IntoEvent, Resources, SharedHandler};
+use crate::{DeadLetter, DeadLetterId, DeadLetterQueue, Event, EventMiddleware, EventNext, HandlerOutcome};
+use crate::{FromResources, IntoCommand, IntoEvent, IntoStreamCommand, ItemStream, Resources, SharedHandler};
+use crate::{Next, Pipelines, Request};
+use crate::{Runtime, StreamHandlerWrapperTrait, Subscribers, Subscription, SubscriptionConfig};
+use futures::StreamExt;
 use std::any::TypeId;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
-type EventQueueItem = Box<dyn EventWrapperTrait + Send + Sync>;
+type EventQueueItem = Event;
+
+/// Events which are recorded instead of processed, used by the `RecordingBus`
+#[cfg(feature = "testing")]
+pub(crate) type RecordedEvents = Arc<std::sync::Mutex<Vec<Event>>>;
 
 #[derive(Clone, MediRessource)]
 pub struct Bus {
     req_handlers: SharedHandler<Arc<dyn HandlerWrapperTrait>>,
     evt_handlers: SharedHandler<Vec<Arc<dyn HandlerWrapperTrait>>>,
+    stream_handlers: SharedHandler<Arc<dyn StreamHandlerWrapperTrait>>,
     resources: Resources,
+    pipelines: Pipelines,
+    evt_middlewares: Vec<Arc<dyn EventMiddleware>>,
+    dead_letters: DeadLetterQueue,
+    subscribers: Subscribers,
+    runtime: Runtime,
     pending_events: Sender<EventQueueItem>,
+    event_loop: Arc<EventLoop>,
+    #[cfg(feature = "testing")]
+    recorded_events: Option<RecordedEvents>,
 }
 
 impl Bus {
     pub fn builder() -> BusBuilder {
         BusBuilder::default()
     }
+
+    /// Handle to call the bus from synchronous code, see `BusHandle`
+    pub fn handle(&self) -> BusHandle {
+        BusHandle::new(self.clone())
+    }
 }
 
 impl Bus {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         resources: Resources,
         req_handlers: SharedHandler<Arc<dyn HandlerWrapperTrait>>,
         evt_handlers: SharedHandler<Vec<Arc<dyn HandlerWrapperTrait>>>,
+        stream_handlers: SharedHandler<Arc<dyn StreamHandlerWrapperTrait>>,
+        pipelines: Pipelines,
+        evt_middlewares: Vec<Arc<dyn EventMiddleware>>,
+        dead_letters: DeadLetterQueue,
+        event_loop_mode: EventLoopMode,
+        runtime: Runtime,
+        #[cfg(feature = "testing")] recorded_events: Option<RecordedEvents>,
     ) -> Self {
-        let (tx, rx) = mpsc::channel(1024);
+        // nothing receives the events in manual mode until `run_until_idle`, a full queue would block the publisher
+        let (tx, rx) = match event_loop_mode {
+            EventLoopMode::Manual => async_channel::unbounded(),
+            EventLoopMode::Background | EventLoopMode::External => async_channel::bounded(1024),
+        };
         let mut bus = Bus {
             req_handlers,
             evt_handlers,
+            stream_handlers,
             resources,
+            pipelines,
+            evt_middlewares,
+            dead_letters,
+            subscribers: Subscribers::default(),
+            runtime,
             pending_events: tx,
+            event_loop: Arc::new(EventLoop::new(rx, event_loop_mode)),
+            #[cfg(feature = "testing")]
+            recorded_events,
         };
 
         // add bus to resources
         bus.resources.insert(bus.clone());
 
         // start processing events
-        bus.start_processing_events(rx);
+        bus.event_loop.start(bus.clone());
 
         bus
     }
 
+    /// Send a request through the registered behaviors to its handler and wait for the response
     pub async fn send<Req, Res>(&self, req: Req) -> Result<Res>
     where
         Req: IntoCommand<Res> + Send + Sync + 'static,
@@ -60,12 +112,16 @@ impl Bus {
         let type_id = TypeId::of::<Req>();
 
         let handler = self.req_handlers.get(&type_id);
-        let Some(handler) = handler else {
-            return Err(Error::HandlerNotFound);
+        let behaviors = self.pipelines.for_request(&type_id);
+        let next = Next::new(behaviors, handler, &self.resources);
+        let res = next.run(Request::new(req));
+        #[cfg(feature = "tracing")]
+        let res = {
+            use tracing::Instrument;
+            let span = tracing::info_span!("medi.send", request = std::any::type_name::<Req>());
+            res.instrument(span)
         };
-
-        let req = Box::new(req);
-        let res = handler.handle(self.resources.clone(), req).await?;
+        let res = res.await?;
 
         let Ok(res) = res.downcast::<Res>() else {
             let type_name = std::any::type_name::<Res>();
@@ -75,13 +131,41 @@ impl Bus {
         Ok(*res)
     }
 
+    /// Send a request to its stream handler and return the stream of items.
+    /// Behaviors, retry policies and timeouts do not apply to stream handlers.
+    pub fn send_stream<Req, Item>(&self, req: Req) -> Result<ItemStream<Item>>
+    where
+        Req: IntoStreamCommand<Item> + Send + Sync + 'static,
+        Item: Send + 'static,
+    {
+        let Some(handler) = self.stream_handlers.get(&TypeId::of::<Req>()) else {
+            return Err(Error::handler_not_found::<Req>());
+        };
+
+        let stream = handler.handle(self.resources.clone(), Box::new(req))?;
+        let Ok(stream) = stream.downcast::<ItemStream<Item>>() else {
+            let type_name = std::any::type_name::<Item>();
+            return Err(Error::CastError(type_name.to_string()));
+        };
+
+        Ok(*stream)
+    }
+
     /// Publish an event without waiting for handlers to complete (fire-and-forget)
     pub async fn publish<Evt>(&self, evt: Evt) -> Result<()>
     where
         Evt: IntoEvent + Clone + Send + Sync + 'static,
     {
+        if !self.event_loop.is_accepting() {
+            return Err(Error::EventPublishingError);
+        }
+
         let event_wrapper = EventWrapper::new(evt);
-        let event_item = event_wrapper.into_dyn();
+        let event_item = Event::new(event_wrapper.into_dyn());
+        #[cfg(feature = "testing")]
+        let Err(event_item) = self.record(event_item) else {
+            return Ok(());
+        };
         self.pending_events
             .send(event_item)
             .await
@@ -90,42 +174,197 @@ impl Bus {
         Ok(())
     }
 
-    fn start_processing_events(&self, rx: Receiver<EventQueueItem>) {
-        let bus = self.clone();
-        tokio::spawn(async move {
-            process_event_loop(Arc::new(bus), rx).await;
-        });
-    }
-}
+    /// Publish an event and wait until all of its handlers completed.
+    /// Returns `Error::EventProcessingError` with the failed handlers if any handler failed
+    /// and `Error::NoEventHandlerRegistered` if the event has neither a handler nor a subscriber.
+    pub async fn publish_and_wait<Evt>(&self, evt: Evt) -> Result<()>
+    where
+        Evt: IntoEvent + Clone + Send + Sync + 'static,
+    {
+        if !self.event_loop.is_accepting() {
+            return Err(Error::EventPublishingError);
+        }
 
-/// Processes the event loop, handling events as they come in.
-async fn process_event_loop(bus: Arc<Bus>, mut rx: Receiver<EventQueueItem>) {
-    while let Some(event_item) = rx.recv().await {
-        let event_item_type = event_item.get_type_id();
-        let Some(handlers) = bus.evt_handlers.get(&event_item_type) else {
-            eprintln!("Handler not found for event: {:?}", event_item.get_type_id());
-            continue;
+        let event_wrapper = EventWrapper::new(evt);
+        let event_item = Event::new(event_wrapper.into_dyn());
+        #[cfg(feature = "testing")]
+        let Err(event_item) = self.record(event_item) else {
+            return Ok(());
         };
 
-        // Process handlers concurrently for better performance
-        let mut tasks = Vec::with_capacity(handlers.len());
-        for handler in handlers {
-            let evt = event_item.get_any();
-            let handler = handler.clone();
-            let resources = bus.resources.clone();
-            let task = tokio::spawn(async move {
-                if let Err(e) = handler.handle(resources, evt).await {
-                    eprintln!("Error: {:?}", e);
+        let type_id = TypeId::of::<Evt>();
+        if !self.evt_handlers.contains_key(&type_id) && !self.subscribers.contains(&type_id) {
+            return Err(Error::NoEventHandlerRegistered);
+        }
+
+        let outcomes = self.dispatch(event_item).await;
+        let failures: Vec<HandlerFailure> = outcomes
+            .into_iter()
+            .filter_map(|outcome| match outcome.result {
+                Ok(()) => None,
+                Err(error) => Some(HandlerFailure {
+                    handler: outcome.handler,
+                    error,
+                }),
+            })
+            .collect();
+
+        if !failures.is_empty() {
+            return Err(Error::EventProcessingError(failures));
+        }
+
+        Ok(())
+    }
+
+    /// Subscribe to the events of type `Evt` which are published from now on.
+    /// The events are buffered with the default `SubscriptionConfig` until the stream is polled.
+    pub fn subscribe<Evt>(&self) -> Subscription<Evt>
+    where
+        Evt: IntoEvent + Clone + Send + Sync + 'static,
+    {
+        self.subscribe_with(SubscriptionConfig::default())
+    }
+
+    /// Subscribe to the events of type `Evt` with a custom buffer capacity and lag policy
+    pub fn subscribe_with<Evt>(&self, config: SubscriptionConfig) -> Subscription<Evt>
+    where
+        Evt: IntoEvent + Clone + Send + Sync + 'static,
+    {
+        Subscription::new(&self.subscribers, config)
+    }
+
+    /// Wait for the next published event of type `Evt` which matches the predicate.
+    ///
+    /// The subscription is created when this method is called, therefore the returned future also sees the events
+    /// which are published before it is awaited. It resolves as soon as the event is dispatched, its handlers may
+    /// still be running. Returns `Error::EventWaitTimeout` if no matching event arrives within the timeout and
+    /// `Error::EventPublishingError` if the bus is shut down.
+    pub fn wait_for<Evt, P>(
+        &self,
+        predicate: P,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<Evt>> + Send + 'static
+    where
+        Evt: IntoEvent + Clone + Send + Sync + 'static,
+        P: Fn(&Evt) -> bool + Send + 'static,
+    {
+        let mut events = self.subscribe::<Evt>();
+        let runtime = self.runtime.clone();
+        async move {
+            let next = async move {
+                while let Some(evt) = events.next().await {
+                    if predicate(&evt) {
+                        return Ok(evt);
+                    }
                 }
-            });
-            tasks.push(task);
+                Err(Error::EventPublishingError)
+            };
+
+            runtime.timeout(timeout, next).await.ok_or(Error::EventWaitTimeout {
+                event: std::any::type_name::<Evt>(),
+                elapsed: timeout,
+            })?
         }
+    }
+
+    /// List the events which could not be processed by one of their handlers
+    pub fn dead_letters(&self) -> Vec<DeadLetter> {
+        self.dead_letters.store().list()
+    }
+
+    /// Get a single dead letter to inspect it
+    pub fn dead_letter(&self, id: DeadLetterId) -> Option<DeadLetter> {
+        self.dead_letters.store().get(id)
+    }
+
+    /// Deliver the event of a dead letter again to the handler which failed.
+    /// The event is kept as the handler received it, therefore the event middlewares do not run again.
+    /// The dead letter is removed on success, otherwise it is kept with the new error and an increased attempt count.
+    pub async fn redeliver(&self, id: DeadLetterId) -> Result<()> {
+        let Some(letter) = self.dead_letters.store().remove(id) else {
+            return Err(Error::DeadLetterNotFound(id));
+        };
 
-        // Wait for all handlers to complete
-        for task in tasks {
-            if let Err(e) = task.await {
-                eprintln!("Task error: {:?}", e);
+        let handler = self
+            .evt_handlers
+            .get(&letter.event_type_id())
+            .and_then(|handlers| handlers.get(letter.handler_index()))
+            .filter(|handler| handler.name() == letter.handler);
+        let Some(handler) = handler else {
+            let request = letter.event_type();
+            self.dead_letters.store().push(letter);
+            return Err(Error::HandlerNotFound { request });
+        };
+
+        let next = EventNext::new(&[], std::slice::from_ref(handler), None, &self.resources, &self.runtime);
+        let outcome = next.run(letter.to_event()).await.into_iter().next();
+        match outcome.map(|outcome| outcome.result) {
+            Some(Err(e)) => {
+                let error = self.dead_letters.push_again(letter, e);
+                Err(Error::RedeliveryFailed { id, error })
             }
+            _ => Ok(()),
         }
     }
+
+    /// Process the queued events until the queue is empty, including the events published by their handlers.
+    /// Returns the number of processed events.
+    ///
+    /// Only the bus built with `BusBuilder::manual_event_processing` queues the events until this method is called,
+    /// otherwise the events are processed in the background and this method returns immediately.
+    pub async fn run_until_idle(&self) -> usize {
+        self.event_loop.run_until_idle(self).await
+    }
+
+    /// Stop accepting new events and wait for the in-flight event handlers to complete.
+    /// Queued events are not processed anymore and listed in the report.
+    /// If the deadline passes, the in-flight event handlers are cancelled.
+    /// The streams of all subscriptions end afterwards.
+    pub async fn shutdown(&self, deadline: Option<Duration>) -> ShutdownReport {
+        let report = self.event_loop.stop(self, StopMode::Shutdown, deadline).await;
+        self.subscribers.close();
+        report
+    }
+
+    /// Stop accepting new events and wait until all queued events are processed.
+    /// If the deadline passes, the in-flight event handlers are cancelled and the remaining events are listed in the report.
+    /// The streams of all subscriptions end afterwards.
+    pub async fn drain(&self, deadline: Option<Duration>) -> ShutdownReport {
+        let report = self.event_loop.stop(self, StopMode::Drain, deadline).await;
+        self.subscribers.close();
+        report
+    }
+
+    /// Record the event instead of processing it if the bus records its events, otherwise return it
+    #[cfg(feature = "testing")]
+    fn record(&self, event: Event) -> std::result::Result<(), Event> {
+        let Some(recorded_events) = &self.recorded_events else {
+            return Err(event);
+        };
+        recorded_events.lock().unwrap().push(event);
+        Ok(())
+    }
+
+    /// Future of the event loop for the caller to drive, only used with `EventLoopMode::External`
+    pub(crate) fn event_loop_future(&self) -> EventLoopFuture {
+        self.event_loop.external(self.clone())
+    }
+
+    /// Passes the event through the middlewares to all of its subscribers and handlers
+    pub(crate) async fn dispatch(&self, event_item: Event) -> Vec<HandlerOutcome> {
+        let handlers = self
+            .evt_handlers
+            .get(&event_item.type_id())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let next = EventNext::new(
+            &self.evt_middlewares,
+            handlers,
+            Some(&self.subscribers),
+            &self.resources,
+            &self.runtime,
+        );
+        next.run(event_item).await
+    }
 }
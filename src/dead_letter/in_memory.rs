@@ -0,0 +1,28 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use super::{DeadLetter, DeadLetterId, DeadLetterStore};
+
+/// Dead letter store which keeps the dead letters in memory
+#[derive(Default)]
+pub struct InMemoryDeadLetterStore {
+    letters: Mutex<BTreeMap<DeadLetterId, DeadLetter>>,
+}
+
+impl DeadLetterStore for InMemoryDeadLetterStore {
+    fn push(&self, letter: DeadLetter) {
+        self.letters.lock().unwrap().insert(letter.id, letter);
+    }
+
+    fn list(&self) -> Vec<DeadLetter> {
+        self.letters.lock().unwrap().values().cloned().collect()
+    }
+
+    fn get(&self, id: DeadLetterId) -> Option<DeadLetter> {
+        self.letters.lock().unwrap().get(&id).cloned()
+    }
+
+    fn remove(&self, id: DeadLetterId) -> Option<DeadLetter> {
+        self.letters.lock().unwrap().remove(&id)
+    }
+}
@@ -0,0 +1,136 @@
+mod in_memory;
+
+// -- flatten
+pub use in_memory::InMemoryDeadLetterStore;
+
+use std::any::TypeId;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::event::EventWrapperTrait;
+use crate::{Delivery, Error, Event};
+
+/// Unique id of a dead letter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DeadLetterId(pub u64);
+
+impl fmt::Display for DeadLetterId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Event which could not be processed by one of its handlers
+#[derive(Clone)]
+pub struct DeadLetter {
+    pub id: DeadLetterId,
+    /// Name of the handler function which failed
+    pub handler: &'static str,
+    /// Position of the handler among the handlers of the event, the name is not unique, e.g. for closures
+    handler_index: usize,
+    /// Error of the last attempt
+    pub error: Arc<Error>,
+    /// Number of deliveries of the event to the handler, the retries of a retry policy count as one delivery
+    pub attempts: u32,
+    event: Arc<dyn EventWrapperTrait + Send + Sync>,
+}
+
+impl DeadLetter {
+    /// Type name of the event
+    pub fn event_type(&self) -> &'static str {
+        self.event.get_type_name()
+    }
+
+    /// Get a reference to the event if it is of type `Evt`
+    pub fn event<Evt: 'static>(&self) -> Option<&Evt> {
+        self.event.as_any().downcast_ref::<Evt>()
+    }
+
+    pub(crate) fn event_type_id(&self) -> TypeId {
+        self.event.get_type_id()
+    }
+
+    pub(crate) fn handler_index(&self) -> usize {
+        self.handler_index
+    }
+
+    /// Copy of the event as the failed handler received it
+    pub(crate) fn to_event(&self) -> Event {
+        Event::new(self.event.clone_dyn())
+    }
+}
+
+impl fmt::Debug for DeadLetter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeadLetter")
+            .field("id", &self.id)
+            .field("event_type", &self.event_type())
+            .field("handler", &self.handler)
+            .field("error", &self.error)
+            .field("attempts", &self.attempts)
+            .finish()
+    }
+}
+
+/// DeadLetterStore trait will be used to keep the events of failed event handlers.
+/// The default store keeps them in memory, see `InMemoryDeadLetterStore`.
+pub trait DeadLetterStore: Send + Sync + 'static {
+    fn push(&self, letter: DeadLetter);
+
+    /// List all dead letters ordered by their id
+    fn list(&self) -> Vec<DeadLetter>;
+
+    fn get(&self, id: DeadLetterId) -> Option<DeadLetter>;
+
+    fn remove(&self, id: DeadLetterId) -> Option<DeadLetter>;
+}
+
+/// Assigns the ids of the dead letters and passes them to the store
+#[derive(Clone)]
+pub(crate) struct DeadLetterQueue {
+    store: Arc<dyn DeadLetterStore>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl DeadLetterQueue {
+    pub(crate) fn new(store: Arc<dyn DeadLetterStore>) -> Self {
+        DeadLetterQueue {
+            store,
+            next_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    pub(crate) fn push(&self, delivery: Delivery, handler: &'static str, error: Error, attempts: u32) {
+        let id = DeadLetterId(self.next_id.fetch_add(1, Ordering::SeqCst));
+        self.store.push(DeadLetter {
+            id,
+            handler,
+            handler_index: delivery.index,
+            error: Arc::new(error),
+            attempts,
+            event: delivery.event,
+        });
+    }
+
+    /// Push a dead letter again after a failed redelivery
+    pub(crate) fn push_again(&self, letter: DeadLetter, error: Error) -> Arc<Error> {
+        let error = Arc::new(error);
+        self.store.push(DeadLetter {
+            error: error.clone(),
+            attempts: letter.attempts + 1,
+            ..letter
+        });
+        error
+    }
+
+    pub(crate) fn store(&self) -> &dyn DeadLetterStore {
+        self.store.as_ref()
+    }
+}
+
+impl Default for DeadLetterQueue {
+    fn default() -> Self {
+        DeadLetterQueue::new(Arc::new(InMemoryDeadLetterStore::default()))
+    }
+}
@@ -0,0 +1,12 @@
+/// Report an error which can not be returned to a caller, e.g. from the event loop.
+/// The error is emitted as tracing event if the `tracing` feature is enabled, otherwise it is printed to stderr.
+macro_rules! report_error {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::error!($($arg)*);
+        #[cfg(not(feature = "tracing"))]
+        eprintln!($($arg)*);
+    }};
+}
+
+pub(crate) use report_error;
@@ -1,9 +1,17 @@
 use thiserror::Error;
 
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::DeadLetterId;
+
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("Handler not found")]
-    HandlerNotFound,
+    #[error("Handler not found for '{request}'")]
+    HandlerNotFound {
+        /// Type name of the request or event
+        request: &'static str,
+    },
 
     #[error("Can not cast to the required type '{0}'")]
     CastError(String),
@@ -11,17 +19,70 @@ pub enum Error {
     #[error("Handler error {0}")]
     Handler(Box<dyn std::error::Error + Send + Sync>),
 
-    #[error("Resource not found")]
-    ResourceNotFound,
+    #[error("Resource '{resource}' not found{}", .handler.map(|handler| format!(" for handler '{handler}'")).unwrap_or_default())]
+    ResourceNotFound {
+        resource: &'static str,
+        /// Name of the handler function which extracts the resource, if known
+        handler: Option<&'static str>,
+    },
 
     #[error("No event handler registered")]
     NoEventHandlerRegistered,
 
-    #[error("Event Processing Error")]
-    EventProcessingError,
+    #[error("Event Processing Error: {} handler(s) failed", .0.len())]
+    EventProcessingError(Vec<HandlerFailure>),
+
+    #[error("Handler panicked")]
+    HandlerPanicked,
+
+    #[error("Handler '{handler}' timed out after {elapsed:?}")]
+    Timeout { handler: &'static str, elapsed: Duration },
+
+    #[error("No event '{event}' received within {elapsed:?}")]
+    EventWaitTimeout { event: &'static str, elapsed: Duration },
 
     #[error("Event Publishing Error")]
     EventPublishingError,
+
+    #[error("No spawner configured, enable a runtime feature or set one with `BusBuilder::spawner`")]
+    NoSpawnerConfigured,
+
+    #[error("Several handlers for request '{request}': {}", .handlers.join(", "))]
+    DuplicateHandler {
+        request: &'static str,
+        handlers: Vec<&'static str>,
+    },
+
+    #[error("Resource '{resource}' of handler '{handler}' for '{request}' is not appended to the bus")]
+    MissingResource {
+        handler: &'static str,
+        request: &'static str,
+        resource: &'static str,
+    },
+
+    #[error("Invalid bus configuration: {}", join_errors(.0))]
+    InvalidConfiguration(Vec<Error>),
+
+    #[error("The runtime of the bus is shut down")]
+    RuntimeUnavailable,
+
+    #[error("Dead letter '{0}' not found")]
+    DeadLetterNotFound(DeadLetterId),
+
+    #[error("Redelivery of dead letter '{id}' failed: {error}")]
+    RedeliveryFailed { id: DeadLetterId, error: Arc<Error> },
+}
+
+fn join_errors(errors: &[Error]) -> String {
+    errors.iter().map(Error::to_string).collect::<Vec<_>>().join("; ")
+}
+
+/// Failure of a single event handler
+#[derive(Debug)]
+pub struct HandlerFailure {
+    /// Name of the handler function
+    pub handler: &'static str,
+    pub error: Error,
 }
 
 /// Handler result type
@@ -29,13 +90,82 @@ pub enum Error {
 pub type Result<T> = core::result::Result<T, Error>;
 
 impl Error {
+    /// Error for a request or event of type `T` without handler
+    pub(crate) fn handler_not_found<T>() -> Self {
+        Error::HandlerNotFound {
+            request: std::any::type_name::<T>(),
+        }
+    }
+
+    /// Error for a resource of type `T` which is not available
+    pub(crate) fn resource_not_found<T>() -> Self {
+        Error::ResourceNotFound {
+            resource: std::any::type_name::<T>(),
+            handler: None,
+        }
+    }
+
+    /// Add the name of the handler `H` to a resource error which does not name its handler yet
+    pub(crate) fn in_handler<H>(self) -> Self {
+        match self {
+            Error::ResourceNotFound {
+                resource,
+                handler: None,
+            } => Error::ResourceNotFound {
+                resource,
+                handler: Some(std::any::type_name::<H>()),
+            },
+            error => error,
+        }
+    }
+
+    /// No handler is registered for the request or event, independent of its type name
+    pub fn is_handler_not_found(&self) -> bool {
+        matches!(self, Error::HandlerNotFound { .. })
+    }
+
+    /// A resource which is extracted by a handler is not available, independent of its type name
+    pub fn is_resource_not_found(&self) -> bool {
+        matches!(self, Error::ResourceNotFound { .. })
+    }
+
     /// Get the handler error if it is a handler error
     pub fn get_handler_error<T: std::error::Error + Send + Sync + 'static>(&self) -> Option<&T> {
         match self {
-            Error::Handler(handler_error) => handler_error.downcast_ref::<T>(),
+            Error::Handler(handler_error) => {
+                #[cfg(feature = "anyhow")]
+                if let Some(crate::AnyhowError(error)) = handler_error.downcast_ref() {
+                    return error.downcast_ref::<T>();
+                }
+                handler_error.downcast_ref::<T>()
+            }
+            Error::RedeliveryFailed { error, .. } => error.get_handler_error::<T>(),
             _ => None,
         }
     }
+
+    /// Take the handler error if it is a handler error of type `T`, otherwise the error is returned unchanged
+    pub fn downcast_handler_error<T: std::error::Error + Send + Sync + 'static>(self) -> core::result::Result<T, Self> {
+        let Error::Handler(handler_error) = self else {
+            return Err(self);
+        };
+
+        #[cfg(feature = "anyhow")]
+        let handler_error = match handler_error.downcast::<crate::AnyhowError>() {
+            Ok(error) => {
+                return error
+                    .0
+                    .downcast::<T>()
+                    .map_err(|error| Error::Handler(Box::new(crate::AnyhowError(error))));
+            }
+            Err(handler_error) => handler_error,
+        };
+
+        handler_error
+            .downcast::<T>()
+            .map(|error| *error)
+            .map_err(Error::Handler)
+    }
 }
 
 #[cfg(test)]
@@ -47,10 +177,36 @@ mod tests {
 
     #[test]
     fn test_get_handler_error_should_return_none() {
-        let error = Error::HandlerNotFound;
+        let error = Error::handler_not_found::<TestError>();
 
         let handler_error = error.get_handler_error::<TestError>();
 
         assert!(handler_error.is_none());
     }
+
+    #[test]
+    fn test_resource_not_found_should_name_resource_and_handler() {
+        let error = Error::resource_not_found::<String>().in_handler::<TestError>();
+
+        assert!(error.is_resource_not_found());
+        assert_eq!(
+            error.to_string(),
+            "Resource 'alloc::string::String' not found for handler 'medi_rs::error::tests::TestError'"
+        );
+    }
+
+    #[test]
+    fn test_in_handler_should_keep_handler_of_nested_error() {
+        let error = Error::resource_not_found::<String>()
+            .in_handler::<u32>()
+            .in_handler::<TestError>();
+
+        assert!(matches!(
+            error,
+            Error::ResourceNotFound {
+                handler: Some("u32"),
+                ..
+            }
+        ));
+    }
 }
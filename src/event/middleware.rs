@@ -0,0 +1,199 @@
+use std::{
+    any::{Any, TypeId},
+    fmt,
+    pin::Pin,
+    sync::Arc,
+};
+
+use super::EventWrapperTrait;
+use crate::diagnostics::report_error;
+use crate::handler_wrapper::HandlerWrapperTrait;
+use crate::{Error, Resources, Result, Runtime, Subscribers};
+
+/// Future returned by an event middleware and by the `next` continuation
+pub type EventMiddlewareFuture<'a> = Pin<Box<dyn futures::Future<Output = Vec<HandlerOutcome>> + Send + 'a>>;
+
+/// EventMiddleware trait will be used to run cross-cutting logic around the fan-out of an event.
+///
+/// A middleware receives the type erased event and the `next` continuation.
+/// Calling `next.run(evt)` runs the remaining middlewares and finally all event handlers,
+/// the returned outcomes contain the result of each handler.
+/// Not calling it filters the event, it will not reach any handler.
+pub trait EventMiddleware: Send + Sync + 'static {
+    fn handle<'a>(&'a self, evt: Event, next: EventNext<'a>) -> EventMiddlewareFuture<'a>;
+}
+
+/// Type erased event which is passed through the event middlewares
+pub struct Event {
+    inner: Box<dyn EventWrapperTrait + Send + Sync>,
+    /// Span which was active while the event was published, parent of the handler spans
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl Event {
+    pub(crate) fn new(inner: Box<dyn EventWrapperTrait + Send + Sync>) -> Self {
+        Event {
+            inner,
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::current(),
+        }
+    }
+
+    /// Type id of the event
+    pub fn type_id(&self) -> TypeId {
+        self.inner.get_type_id()
+    }
+
+    /// Type name of the event, e.g. for logging
+    pub fn type_name(&self) -> &'static str {
+        self.inner.get_type_name()
+    }
+
+    /// Get a reference to the event if it is of type `T`
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.inner.as_any().downcast_ref::<T>()
+    }
+
+    /// Get a mutable reference to the event if it is of type `T`, e.g. to enrich it
+    pub fn downcast_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.inner.as_any_mut().downcast_mut::<T>()
+    }
+
+    pub(crate) fn get_any(&self) -> Box<dyn Any + Send + Sync> {
+        self.inner.get_any()
+    }
+
+    pub(crate) fn clone_inner(&self) -> Arc<dyn EventWrapperTrait + Send + Sync> {
+        Arc::from(self.inner.clone_dyn())
+    }
+}
+
+/// Result of a single event handler
+#[derive(Debug)]
+pub struct HandlerOutcome {
+    /// Name of the handler function
+    pub handler: &'static str,
+    pub result: Result<()>,
+    /// Only set if the handler failed, kept as dead letter by the event loop
+    pub(crate) delivery: Option<Delivery>,
+}
+
+/// Event as a failed handler received it, after the middlewares enriched it
+pub(crate) struct Delivery {
+    /// Position of the handler among the handlers of the event
+    pub(crate) index: usize,
+    pub(crate) event: Arc<dyn EventWrapperTrait + Send + Sync>,
+}
+
+impl fmt::Debug for Delivery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Delivery")
+            .field("index", &self.index)
+            .field("event_type", &self.event.get_type_name())
+            .finish()
+    }
+}
+
+/// Continuation to the remaining middlewares and the event handlers
+pub struct EventNext<'a> {
+    middlewares: &'a [Arc<dyn EventMiddleware>],
+    handlers: &'a [Arc<dyn HandlerWrapperTrait>],
+    /// Not set on redelivery, the subscribers already received the event
+    subscribers: Option<&'a Subscribers>,
+    resources: &'a Resources,
+    runtime: &'a Runtime,
+}
+
+impl<'a> EventNext<'a> {
+    pub(crate) fn new(
+        middlewares: &'a [Arc<dyn EventMiddleware>],
+        handlers: &'a [Arc<dyn HandlerWrapperTrait>],
+        subscribers: Option<&'a Subscribers>,
+        resources: &'a Resources,
+        runtime: &'a Runtime,
+    ) -> Self {
+        EventNext {
+            middlewares,
+            handlers,
+            subscribers,
+            resources,
+            runtime,
+        }
+    }
+
+    /// Run the next middleware or fan out the event to all subscribers and handlers if no middleware is left
+    pub fn run(self, evt: Event) -> EventMiddlewareFuture<'a> {
+        if let Some((middleware, middlewares)) = self.middlewares.split_first() {
+            let next = EventNext {
+                middlewares,
+                handlers: self.handlers,
+                subscribers: self.subscribers,
+                resources: self.resources,
+                runtime: self.runtime,
+            };
+            return middleware.handle(evt, next);
+        }
+
+        Box::pin(async move {
+            let subscribed = self.subscribers.is_some_and(|subscribers| subscribers.deliver(&evt));
+            if self.handlers.is_empty() {
+                if !subscribed {
+                    report_error!("Handler not found for event: {}", evt.type_name());
+                }
+                return Vec::new();
+            }
+
+            // Process handlers concurrently for better performance,
+            // dropping the task handles cancels the handlers, e.g. at the shutdown deadline
+            let mut tasks = Vec::with_capacity(self.handlers.len());
+            for handler in self.handlers {
+                let value = evt.get_any();
+                let name = handler.name();
+                let handler = handler.clone();
+                let resources = self.resources.clone();
+                let task = async move { handler.handle(resources, value).await.map(|_| ()) };
+                #[cfg(feature = "tracing")]
+                let task = {
+                    use tracing::Instrument;
+                    let span = tracing::info_span!(
+                        parent: &evt.span,
+                        "medi.event_handler",
+                        event = evt.type_name(),
+                        handler = name
+                    );
+                    task.instrument(span)
+                };
+                tasks.push((name, self.runtime.spawn(task)));
+            }
+
+            // Wait for all handlers to complete, the event is only copied if a handler failed
+            let mut event = None;
+            let mut outcomes = Vec::with_capacity(tasks.len());
+            for (index, (handler, mut task)) in tasks.into_iter().enumerate() {
+                let result = match task.join().await {
+                    Some(Ok(result)) => result,
+                    Some(Err(_)) => {
+                        report_error!("Event handler panicked: {}", handler);
+                        Err(Error::HandlerPanicked)
+                    }
+                    None => {
+                        report_error!("Event handler was dropped by the runtime: {}", handler);
+                        Err(Error::HandlerPanicked)
+                    }
+                };
+                let delivery = result.is_err().then(|| Delivery {
+                    index,
+                    event: event.get_or_insert_with(|| evt.clone_inner()).clone(),
+                });
+                outcomes.push(HandlerOutcome {
+                    handler,
+                    result,
+                    delivery,
+                });
+            }
+
+            outcomes
+        })
+    }
+}
@@ -1,9 +1,15 @@
+mod middleware;
+
+// -- flatten
+pub use middleware::*;
+
 use std::any::{Any, TypeId};
 
 use crate::IntoEvent;
 
 pub(crate) struct EventWrapper<Evt> {
     type_id: TypeId,
+    type_name: &'static str,
     event: Evt,
 }
 
@@ -14,6 +20,7 @@ where
     pub(crate) fn new(event: Evt) -> Self {
         EventWrapper {
             type_id: TypeId::of::<Evt>(),
+            type_name: std::any::type_name::<Evt>(),
             event,
         }
     }
@@ -24,7 +31,15 @@ pub(crate) trait EventWrapperTrait: Send + Sync {
 
     fn get_type_id(&self) -> TypeId;
 
+    fn get_type_name(&self) -> &'static str;
+
     fn get_any(&self) -> Box<dyn Any + Send + Sync>;
+
+    fn clone_dyn(&self) -> Box<dyn EventWrapperTrait + Send + Sync + 'static>;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
 impl<Evt> EventWrapperTrait for EventWrapper<Evt>
@@ -39,7 +54,27 @@ where
         Box::new(self.event.clone())
     }
 
+    fn clone_dyn(&self) -> Box<dyn EventWrapperTrait + Send + Sync + 'static> {
+        Box::new(EventWrapper {
+            type_id: self.type_id,
+            type_name: self.type_name,
+            event: self.event.clone(),
+        })
+    }
+
     fn get_type_id(&self) -> TypeId {
         self.type_id
     }
+
+    fn get_type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        &self.event
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        &mut self.event
+    }
 }
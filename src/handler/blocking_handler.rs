@@ -0,0 +1,101 @@
+use std::any::Any;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use super::handler_wrapper::HandlerWrapperTrait;
+use crate::{Error, ResourceType, Resources, Result, Runtime};
+
+/// Synchronous handler which is executed on the blocking thread pool of the runtime,
+/// e.g. for CPU-heavy or legacy code which would block the event loop
+pub trait BlockingHandler<T, Req, Res>: Clone
+where
+    T: Send + Sync + 'static,
+    Req: Send + Sync + 'static,
+    Res: Send + Sync + 'static,
+{
+    fn handle(self, resources: Resources, value: Req) -> Result<Res>;
+
+    /// Resources which are extracted for the handler
+    fn resource_types() -> Vec<ResourceType>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+
+    #[allow(private_interfaces)]
+    fn into_dyn(self) -> Arc<dyn BlockingHandlerWrapperTrait>
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        Arc::new(BlockingHandlerWrapper {
+            handler: self,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+#[allow(clippy::type_complexity)]
+struct BlockingHandlerWrapper<H, T, Req, Res> {
+    handler: H,
+    _phantom: PhantomData<fn() -> (T, Req, Res)>,
+}
+
+pub(crate) trait BlockingHandlerWrapperTrait: Send + Sync {
+    /// Name of the handler function, used to identify the handler
+    fn name(&self) -> &'static str;
+
+    fn handle(&self, resources: Resources, value: Box<dyn Any + Send + Sync>) -> Result<Box<dyn Any + Send + Sync>>;
+}
+
+impl<H, TResource, Req, Res> BlockingHandlerWrapperTrait for BlockingHandlerWrapper<H, TResource, Req, Res>
+where
+    H: BlockingHandler<TResource, Req, Res> + Sync + Send + 'static,
+    TResource: Send + Sync + 'static,
+    Req: Sync + Send + 'static,
+    Res: Send + Sync + 'static,
+{
+    fn name(&self) -> &'static str {
+        std::any::type_name::<H>()
+    }
+
+    fn handle(&self, resources: Resources, value: Box<dyn Any + Send + Sync>) -> Result<Box<dyn Any + Send + Sync>> {
+        let Ok(arg) = value.downcast::<Req>() else {
+            let type_name = std::any::type_name::<Req>();
+            return Err(Error::CastError(type_name.to_string()));
+        };
+
+        let res = self.handler.clone().handle(resources, *arg)?;
+        Ok(Box::new(res))
+    }
+}
+
+/// Adapter which runs a blocking handler with the spawner of the bus
+pub(crate) struct SpawnBlockingHandler {
+    inner: Arc<dyn BlockingHandlerWrapperTrait>,
+    runtime: Runtime,
+}
+
+impl SpawnBlockingHandler {
+    pub(crate) fn new(inner: Arc<dyn BlockingHandlerWrapperTrait>, runtime: Runtime) -> Self {
+        SpawnBlockingHandler { inner, runtime }
+    }
+}
+
+impl HandlerWrapperTrait for SpawnBlockingHandler {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn handle(
+        &self,
+        resources: Resources,
+        value: Box<dyn Any + Send + Sync>,
+    ) -> Pin<Box<dyn futures::Future<Output = Result<Box<dyn Any + Send + Sync>>> + Send>> {
+        let inner = self.inner.clone();
+        let res = self.runtime.spawn_blocking(move || inner.handle(resources, value));
+
+        Box::pin(async move { res.await.unwrap_or(Err(Error::HandlerPanicked)) })
+    }
+}
@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+use crate::RetryPolicy;
+
+/// Configuration of a single handler, passed at the registration on the `BusBuilder`.
+/// Unset values fall back to the bus-wide defaults.
+#[derive(Debug, Clone, Default)]
+pub struct HandlerConfig {
+    pub(crate) retry: Option<RetryPolicy>,
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl HandlerConfig {
+    pub fn new() -> Self {
+        HandlerConfig::default()
+    }
+
+    /// Retry the handler according to the policy
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Cancel the handler with `Error::Timeout` if a single attempt takes longer than the timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Take the unset values from the defaults
+    pub(crate) fn or(self, defaults: &HandlerConfig) -> Self {
+        HandlerConfig {
+            retry: self.retry.or_else(|| defaults.retry.clone()),
+            timeout: self.timeout.or(defaults.timeout),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use std::error::Error as StdError;
+
+use crate::Error;
+
+/// Error type which a handler can return, converted into `Error::Handler`.
+/// Implemented for every type which converts into `Box<dyn Error + Send + Sync>`, e.g. error types deriving
+/// `thiserror::Error`, boxed errors and `anyhow::Error`. A `medi_rs::Error` is passed on unchanged.
+pub trait IntoHandlerError {
+    fn into_handler_error(self) -> Error;
+}
+
+impl<E> IntoHandlerError for E
+where
+    E: Into<Box<dyn StdError + Send + Sync>> + 'static,
+{
+    fn into_handler_error(self) -> Error {
+        // an anyhow error loses its concrete type when it is boxed, keep it to be able to downcast it
+        #[cfg(feature = "anyhow")]
+        let this = match take_anyhow(self) {
+            Ok(error) => return from_anyhow(error),
+            Err(this) => this,
+        };
+        #[cfg(not(feature = "anyhow"))]
+        let this = self;
+
+        let error: Box<dyn StdError + Send + Sync> = this.into();
+        match error.downcast::<Error>() {
+            Ok(error) => *error,
+            Err(error) => Error::Handler(error),
+        }
+    }
+}
+
+#[cfg(feature = "anyhow")]
+fn take_anyhow<E: 'static>(error: E) -> std::result::Result<anyhow::Error, E> {
+    let mut error = Some(error);
+    match (&mut error as &mut dyn std::any::Any).downcast_mut::<Option<anyhow::Error>>() {
+        Some(anyhow_error) => Ok(anyhow_error.take().expect("error is set")),
+        None => Err(error.expect("error is set")),
+    }
+}
+
+#[cfg(feature = "anyhow")]
+fn from_anyhow(error: anyhow::Error) -> Error {
+    match error.downcast::<Error>() {
+        Ok(error) => error,
+        Err(error) => Error::Handler(Box::new(AnyhowError(error))),
+    }
+}
+
+/// `anyhow::Error` returned by a handler, its error can be downcast with `Error::get_handler_error`
+#[cfg(feature = "anyhow")]
+#[derive(Debug)]
+pub(crate) struct AnyhowError(pub(crate) anyhow::Error);
+
+#[cfg(feature = "anyhow")]
+impl std::fmt::Display for AnyhowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl StdError for AnyhowError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
+    }
+}
@@ -22,6 +22,9 @@ impl<H, T, Req, Res> HandlerWrapper<H, T, Req, Res> {
 
 #[allow(clippy::type_complexity)]
 pub(crate) trait HandlerWrapperTrait: Send + Sync {
+    /// Name of the handler function, used to identify the handler
+    fn name(&self) -> &'static str;
+
     fn handle(
         &self,
         resources: Resources,
@@ -36,6 +39,10 @@ where
     Req: Sync + Send + 'static,
     Res: Send + Sync + 'static,
 {
+    fn name(&self) -> &'static str {
+        std::any::type_name::<H>()
+    }
+
     fn handle(
         &self,
         resources: Resources,
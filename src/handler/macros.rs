@@ -7,7 +7,7 @@ macro_rules! impl_handler {
             Req: Sync + Send + 'static,
             Res: Sync + Send + 'static,
             $($T: FromResources + Clone + Send + Sync + 'static,)*
-            E: std::error::Error + Sized + Send + Sync + 'static,
+            E: IntoHandlerError,
             Fut: futures::Future<Output = core::result::Result<Res, E>> + Send,
         {
             type Future = std::pin::Pin<Box<dyn futures::Future<Output = Result<Res>> + Send>>;
@@ -16,17 +16,72 @@ macro_rules! impl_handler {
             fn handle(self, resources: resource::Resources, value: Req) -> Self::Future {
                 Box::pin(async move {
                     let arg = value;
-                    let res = self($($T::from_resources(&resources)?,)* arg).await;
-
-                    match res {
-                        Ok(res) => Ok(res),
-                        Err(e) => {
-                            let he = Error::Handler(Box::new(e));
-                            Err(he)
-                        }
-                    }
+                    let res = self($($T::from_resources(&resources).map_err(Error::in_handler::<F>)?,)* arg).await;
+
+                    res.map_err(IntoHandlerError::into_handler_error)
                 })
             }
+
+            fn resource_types() -> Vec<ResourceType> {
+                let types: Vec<Option<ResourceType>> = vec![$($T::resource_type(),)*];
+                types.into_iter().flatten().collect()
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_stream_handler {
+    ($($T:ident), *) => {
+        impl<F, S, $($T,)* Req, Item, E> StreamHandler<($($T,)*), Req, Item> for F
+        where
+            F: FnOnce($($T,)* Req) -> S + Clone + Send + 'static,
+            Req: Sync + Send + 'static,
+            Item: Send + 'static,
+            $($T: FromResources + Clone + Send + Sync + 'static,)*
+            E: IntoHandlerError,
+            S: futures::Stream<Item = core::result::Result<Item, E>> + Send + 'static,
+        {
+            #[allow(unused)]
+            fn handle(self, resources: resource::Resources, value: Req) -> Result<ItemStream<Item>> {
+                let arg = value;
+                let stream = self($($T::from_resources(&resources).map_err(Error::in_handler::<F>)?,)* arg);
+                let stream = futures::StreamExt::map(stream, |item| item.map_err(IntoHandlerError::into_handler_error));
+
+                Ok(Box::pin(stream))
+            }
+
+            fn resource_types() -> Vec<ResourceType> {
+                let types: Vec<Option<ResourceType>> = vec![$($T::resource_type(),)*];
+                types.into_iter().flatten().collect()
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_blocking_handler {
+    ($($T:ident), *) => {
+        impl<F, $($T,)* Req, Res, E> BlockingHandler<($($T,)*), Req, Res> for F
+        where
+            F: FnOnce($($T,)* Req) -> core::result::Result<Res, E> + Clone + Send + 'static,
+            Req: Sync + Send + 'static,
+            Res: Sync + Send + 'static,
+            $($T: FromResources + Clone + Send + Sync + 'static,)*
+            E: IntoHandlerError,
+        {
+            #[allow(unused)]
+            fn handle(self, resources: resource::Resources, value: Req) -> Result<Res> {
+                let arg = value;
+                let res = self($($T::from_resources(&resources).map_err(Error::in_handler::<F>)?,)* arg);
+
+                res.map_err(IntoHandlerError::into_handler_error)
+            }
+
+            fn resource_types() -> Vec<ResourceType> {
+                let types: Vec<Option<ResourceType>> = vec![$($T::resource_type(),)*];
+                types.into_iter().flatten().collect()
+            }
         }
     };
 }
@@ -1,12 +1,25 @@
-// mod handler_error;
+mod blocking_handler;
+mod handler_config;
+mod handler_error;
 pub mod handler_wrapper;
 mod macros;
+mod stream_handler;
+mod timeout_handler;
 
 // --flatten
-// pub use handler_error::*;
+pub use blocking_handler::BlockingHandler;
+pub(crate) use blocking_handler::{BlockingHandlerWrapperTrait, SpawnBlockingHandler};
+pub use handler_config::*;
+#[cfg(feature = "anyhow")]
+pub(crate) use handler_error::AnyhowError;
+pub use handler_error::IntoHandlerError;
 use handler_wrapper::HandlerWrapper;
 use handler_wrapper::HandlerWrapperTrait;
+pub(crate) use stream_handler::StreamHandlerWrapperTrait;
+pub use stream_handler::{ItemStream, StreamHandler};
+pub(crate) use timeout_handler::TimeoutHandler;
 
+use crate::ResourceType;
 use crate::Resources;
 use crate::Result;
 use std::sync::Arc;
@@ -24,6 +37,14 @@ where
 
     fn handle(self, resources: Resources, value: Req) -> Self::Future;
 
+    /// Resources which are extracted for the handler
+    fn resource_types() -> Vec<ResourceType>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+
     #[allow(private_interfaces)]
     fn into_dyn(self) -> Arc<dyn HandlerWrapperTrait>
     where
@@ -32,3 +53,15 @@ where
         Arc::new(HandlerWrapper::new(self)) as Arc<dyn HandlerWrapperTrait>
     }
 }
+
+/// Call a request or event handler directly with the given resources, without a bus.
+/// Behaviors, retry policies and timeouts do not apply.
+pub fn invoke_handler<H, T, Req, Res>(handler: H, resources: &Resources, req: Req) -> H::Future
+where
+    H: Handler<T, Req, Res>,
+    T: Send + Sync + 'static,
+    Req: Send + Sync + 'static,
+    Res: Send + Sync + 'static,
+{
+    handler.handle(resources.clone(), req)
+}
@@ -0,0 +1,83 @@
+use std::{any::Any, marker::PhantomData, sync::Arc};
+
+use futures::stream::BoxStream;
+
+use crate::Error;
+use crate::ResourceType;
+use crate::Resources;
+use crate::Result;
+
+/// Stream of items returned by a stream handler
+pub type ItemStream<Item> = BoxStream<'static, Result<Item>>;
+
+pub trait StreamHandler<T, Req, Item>: Clone
+where
+    T: Send + Sync + 'static,
+    Req: Send + Sync + 'static,
+    Item: Send + 'static,
+{
+    /// Extract the resources and create the stream, the items are produced while the stream is polled
+    fn handle(self, resources: Resources, value: Req) -> Result<ItemStream<Item>>;
+
+    /// Resources which are extracted for the handler
+    fn resource_types() -> Vec<ResourceType>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+
+    #[allow(private_interfaces)]
+    fn into_dyn(self) -> Arc<dyn StreamHandlerWrapperTrait>
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        Arc::new(StreamHandlerWrapper::new(self)) as Arc<dyn StreamHandlerWrapperTrait>
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) struct StreamHandlerWrapper<H, T, Req, Item> {
+    handler: H,
+    _phantom: PhantomData<fn() -> (T, Req, Item)>,
+}
+
+impl<H, T, Req, Item> StreamHandlerWrapper<H, T, Req, Item> {
+    pub(crate) fn new(handler: H) -> Self {
+        StreamHandlerWrapper {
+            handler,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+pub(crate) trait StreamHandlerWrapperTrait: Send + Sync {
+    /// Name of the handler function, used to identify the handler
+    fn name(&self) -> &'static str;
+
+    /// Create the stream of the handler, the returned value is an `ItemStream<Item>`
+    fn handle(&self, resources: Resources, value: Box<dyn Any + Send + Sync>) -> Result<Box<dyn Any + Send>>;
+}
+
+impl<H, TResource, Req, Item> StreamHandlerWrapperTrait for StreamHandlerWrapper<H, TResource, Req, Item>
+where
+    H: StreamHandler<TResource, Req, Item> + Sync + Send + 'static,
+    TResource: Send + Sync + 'static,
+    Req: Sync + Send + 'static,
+    Item: Send + 'static,
+{
+    fn name(&self) -> &'static str {
+        std::any::type_name::<H>()
+    }
+
+    fn handle(&self, resources: Resources, value: Box<dyn Any + Send + Sync>) -> Result<Box<dyn Any + Send>> {
+        let Ok(arg) = value.downcast::<Req>() else {
+            let type_name = std::any::type_name::<Req>();
+            return Err(Error::CastError(type_name.to_string()));
+        };
+
+        let handler = self.handler.clone();
+        let stream = handler.handle(resources, *arg)?;
+        Ok(Box::new(stream))
+    }
+}
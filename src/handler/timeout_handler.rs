@@ -0,0 +1,52 @@
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::handler_wrapper::HandlerWrapperTrait;
+use crate::{Error, Resources, Result, Runtime};
+
+/// Handler decorator which cancels the inner handler when the deadline passes
+pub(crate) struct TimeoutHandler {
+    inner: Arc<dyn HandlerWrapperTrait>,
+    timeout: Duration,
+    runtime: Runtime,
+}
+
+impl TimeoutHandler {
+    pub(crate) fn new(inner: Arc<dyn HandlerWrapperTrait>, timeout: Duration, runtime: Runtime) -> Self {
+        TimeoutHandler {
+            inner,
+            timeout,
+            runtime,
+        }
+    }
+}
+
+impl HandlerWrapperTrait for TimeoutHandler {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn handle(
+        &self,
+        resources: Resources,
+        value: Box<dyn Any + Send + Sync>,
+    ) -> Pin<Box<dyn futures::Future<Output = Result<Box<dyn Any + Send + Sync>>> + Send>> {
+        let handler = self.inner.name();
+        let timeout = self.timeout;
+        let fut = self.inner.handle(resources, value);
+        let runtime = self.runtime.clone();
+
+        Box::pin(async move {
+            let started = Instant::now();
+            match runtime.timeout(timeout, fut).await {
+                Some(res) => res,
+                None => Err(Error::Timeout {
+                    handler,
+                    elapsed: started.elapsed(),
+                }),
+            }
+        })
+    }
+}
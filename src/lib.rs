@@ -1,17 +1,61 @@
+mod behavior;
 mod bus;
+mod dead_letter;
+mod diagnostics;
 mod error;
 mod event;
 mod handler;
+#[cfg(feature = "tokio")]
+mod local;
+#[cfg(feature = "medi-rs-macros")]
+mod registry;
 mod resource;
+mod retry;
+mod runtime;
+mod subscription;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 // flatten the module structure
+pub use behavior::*;
 pub use bus::*;
+pub use dead_letter::*;
 pub use error::*;
+pub use event::*;
 pub use handler::*;
+#[cfg(feature = "tokio")]
+pub use local::*;
+#[cfg(feature = "medi-rs-macros")]
+pub use registry::DeclaredHandler;
+#[cfg(feature = "medi-rs-macros")]
+pub(crate) use registry::{DeclaredHandlerKind, declared_handlers};
 pub use resource::*;
+pub use retry::*;
+pub use runtime::*;
+pub use subscription::*;
+
+/// Used by `#[medi_handler]` to register the handlers at link time
+#[cfg(feature = "medi-rs-macros")]
+#[doc(hidden)]
+pub use inventory;
 
 /// IntoCommand trait will be used to mark command or query types for the bus
 pub trait IntoCommand<Res>
+where
+    Self: Send + Sync + 'static,
+{
+    /// Copy of the request for another attempt of a retry policy.
+    /// `#[derive(MediCommand)]` copies `Clone` requests, by default a request is not copied and not retried.
+    fn clone_request(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// IntoStreamCommand trait will be used to mark requests whose handler returns a stream of items
+pub trait IntoStreamCommand<Item>
 where
     Self: Send + Sync + 'static,
 {
@@ -34,4 +78,22 @@ impl_handler!(T1, T2, T3, T4);
 impl_handler!(T1, T2, T3, T4, T5);
 impl_handler!(T1, T2, T3, T4, T5, T6);
 impl_handler!(T1, T2, T3, T4, T5, T6, T7);
+
+impl_blocking_handler!();
+impl_blocking_handler!(T1);
+impl_blocking_handler!(T1, T2);
+impl_blocking_handler!(T1, T2, T3);
+impl_blocking_handler!(T1, T2, T3, T4);
+impl_blocking_handler!(T1, T2, T3, T4, T5);
+impl_blocking_handler!(T1, T2, T3, T4, T5, T6);
+impl_blocking_handler!(T1, T2, T3, T4, T5, T6, T7);
+
+impl_stream_handler!();
+impl_stream_handler!(T1);
+impl_stream_handler!(T1, T2);
+impl_stream_handler!(T1, T2, T3);
+impl_stream_handler!(T1, T2, T3, T4);
+impl_stream_handler!(T1, T2, T3, T4, T5);
+impl_stream_handler!(T1, T2, T3, T4, T5, T6);
+impl_stream_handler!(T1, T2, T3, T4, T5, T6, T7);
 //-- endregion: Implement the handler traits
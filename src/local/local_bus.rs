@@ -0,0 +1,275 @@
+use std::any::{Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
+use std::pin::pin;
+use std::rc::Rc;
+use std::time::Duration;
+
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures::channel::oneshot;
+use futures::future::Either;
+use futures::{FutureExt, StreamExt};
+use medi_rs_macros::MediRessource;
+use tokio::task::JoinHandle;
+
+use super::{LocalBusBuilder, LocalHandlerWrapperTrait, LocalResources};
+use crate::diagnostics::report_error;
+use crate::{Error, FromResources, HandlerFailure, HandlerOutcome, IntoCommand, IntoEvent, Result, ShutdownReport};
+
+/// Creates a copy of the type erased event for each of its handlers
+type CloneEventFn = fn(&dyn Any) -> Box<dyn Any>;
+
+fn clone_event<Evt: Clone + 'static>(evt: &dyn Any) -> Box<dyn Any> {
+    let evt = evt
+        .downcast_ref::<Evt>()
+        .expect("event type matches its clone function");
+    Box::new(evt.clone())
+}
+
+/// Type erased event of the local event loop
+struct LocalEvent {
+    type_id: TypeId,
+    type_name: &'static str,
+    value: Box<dyn Any>,
+    clone_value: CloneEventFn,
+}
+
+/// State shared between the bus and its event loop task
+#[derive(Default)]
+struct LocalEventLoop {
+    /// Set by `LocalBus::shutdown`, the queued events are left unprocessed
+    stopped: Cell<bool>,
+    /// Cancels the in-flight event handlers when the deadline of a shutdown passed
+    cancel: Cell<Option<oneshot::Sender<()>>>,
+    task: RefCell<Option<JoinHandle<Vec<&'static str>>>>,
+}
+
+/// Bus for single-threaded runtimes whose handlers, resources and futures do not have to be `Send`.
+///
+/// The event loop runs on the current tokio `LocalSet`, therefore the bus has to be built inside of it.
+/// The handlers of an event run concurrently on the event loop task.
+/// The event loop runs until `LocalBus::shutdown` or `LocalBus::drain` is called or the `LocalSet` is dropped.
+/// Behaviors, event middlewares, retry policies, timeouts and dead letters are only available for `Bus`.
+#[derive(Clone, MediRessource)]
+pub struct LocalBus {
+    req_handlers: Rc<HashMap<TypeId, Rc<dyn LocalHandlerWrapperTrait>>>,
+    evt_handlers: Rc<HashMap<TypeId, Vec<Rc<dyn LocalHandlerWrapperTrait>>>>,
+    resources: LocalResources,
+    pending_events: UnboundedSender<LocalEvent>,
+    event_loop: Rc<LocalEventLoop>,
+}
+
+impl LocalBus {
+    pub fn builder() -> LocalBusBuilder {
+        LocalBusBuilder::default()
+    }
+
+    pub(crate) fn new(
+        resources: LocalResources,
+        req_handlers: HashMap<TypeId, Rc<dyn LocalHandlerWrapperTrait>>,
+        evt_handlers: HashMap<TypeId, Vec<Rc<dyn LocalHandlerWrapperTrait>>>,
+    ) -> Self {
+        let (tx, rx) = mpsc::unbounded::<LocalEvent>();
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        let mut bus = LocalBus {
+            req_handlers: Rc::new(req_handlers),
+            evt_handlers: Rc::new(evt_handlers),
+            resources,
+            pending_events: tx,
+            event_loop: Rc::default(),
+        };
+        bus.event_loop.cancel.set(Some(cancel_tx));
+
+        // add bus to resources
+        bus.resources.insert(bus.clone());
+
+        // start processing events
+        let task = tokio::task::spawn_local(process_event_loop(bus.clone(), rx, cancel_rx));
+        *bus.event_loop.task.borrow_mut() = Some(task);
+
+        bus
+    }
+
+    /// Send a request to its handler and wait for the response
+    pub async fn send<Req, Res>(&self, req: Req) -> Result<Res>
+    where
+        Req: IntoCommand<Res>,
+        Res: 'static,
+    {
+        let Some(handler) = self.req_handlers.get(&TypeId::of::<Req>()) else {
+            return Err(Error::handler_not_found::<Req>());
+        };
+
+        let res = handler.handle(self.resources.clone(), Box::new(req)).await?;
+        let Ok(res) = res.downcast::<Res>() else {
+            let type_name = std::any::type_name::<Res>();
+            return Err(Error::CastError(type_name.to_string()));
+        };
+
+        Ok(*res)
+    }
+
+    /// Publish an event without waiting for handlers to complete (fire-and-forget)
+    pub async fn publish<Evt>(&self, evt: Evt) -> Result<()>
+    where
+        Evt: IntoEvent + Clone,
+    {
+        self.pending_events
+            .unbounded_send(LocalEvent::new(evt))
+            .map_err(|_| Error::EventPublishingError)
+    }
+
+    /// Publish an event and wait until all of its handlers completed.
+    /// Returns `Error::EventProcessingError` with the failed handlers if any handler failed.
+    pub async fn publish_and_wait<Evt>(&self, evt: Evt) -> Result<()>
+    where
+        Evt: IntoEvent + Clone,
+    {
+        if self.pending_events.is_closed() {
+            return Err(Error::EventPublishingError);
+        }
+
+        if !self.evt_handlers.contains_key(&TypeId::of::<Evt>()) {
+            return Err(Error::NoEventHandlerRegistered);
+        }
+
+        let failures: Vec<HandlerFailure> = self
+            .dispatch(LocalEvent::new(evt))
+            .await
+            .into_iter()
+            .filter_map(|outcome| match outcome.result {
+                Ok(()) => None,
+                Err(error) => Some(HandlerFailure {
+                    handler: outcome.handler,
+                    error,
+                }),
+            })
+            .collect();
+
+        if !failures.is_empty() {
+            return Err(Error::EventProcessingError(failures));
+        }
+
+        Ok(())
+    }
+
+    /// Stop accepting new events and wait for the in-flight event handlers to complete.
+    /// Queued events are not processed anymore and listed in the report.
+    /// If the deadline passes, the in-flight event handlers are cancelled.
+    pub async fn shutdown(&self, deadline: Option<Duration>) -> ShutdownReport {
+        self.event_loop.stopped.set(true);
+        self.stop(deadline).await
+    }
+
+    /// Stop accepting new events and wait until all queued events are processed.
+    /// If the deadline passes, the in-flight event handlers are cancelled and the remaining events are listed in the report.
+    pub async fn drain(&self, deadline: Option<Duration>) -> ShutdownReport {
+        self.stop(deadline).await
+    }
+
+    /// Close the event queue, which ends the event loop, and wait for it
+    async fn stop(&self, deadline: Option<Duration>) -> ShutdownReport {
+        self.pending_events.close_channel();
+
+        let mut report = ShutdownReport::default();
+        let task = self.event_loop.task.borrow_mut().take();
+        let Some(mut task) = task else {
+            return report;
+        };
+
+        let unprocessed = match deadline {
+            Some(deadline) => match tokio::time::timeout(deadline, &mut task).await {
+                Ok(unprocessed) => unprocessed,
+                Err(_) => {
+                    report.deadline_exceeded = true;
+                    if let Some(cancel) = self.event_loop.cancel.take() {
+                        let _ = cancel.send(());
+                    }
+                    task.await
+                }
+            },
+            None => task.await,
+        };
+        report.unprocessed_events = unprocessed.unwrap_or_default();
+
+        report
+    }
+
+    /// Runs all handlers of the event concurrently and waits for their outcomes
+    async fn dispatch(&self, evt: LocalEvent) -> Vec<HandlerOutcome> {
+        let Some(handlers) = self.evt_handlers.get(&evt.type_id) else {
+            report_error!("Handler not found for event: {}", evt.type_name);
+            return Vec::new();
+        };
+
+        let tasks = handlers.iter().map(|handler| {
+            let value = (evt.clone_value)(evt.value.as_ref());
+            let task = handler.handle(self.resources.clone(), value);
+            async move {
+                let result = match AssertUnwindSafe(task).catch_unwind().await {
+                    Ok(result) => result.map(|_| ()),
+                    Err(_) => {
+                        report_error!("Event handler panicked: {}", handler.name());
+                        Err(Error::HandlerPanicked)
+                    }
+                };
+                HandlerOutcome {
+                    handler: handler.name(),
+                    result,
+                    delivery: None,
+                }
+            }
+        });
+
+        futures::future::join_all(tasks).await
+    }
+}
+
+/// Processes the events until the queue is closed, returns the type names of the events which were left unprocessed
+async fn process_event_loop(
+    bus: LocalBus,
+    mut rx: UnboundedReceiver<LocalEvent>,
+    mut cancel: oneshot::Receiver<()>,
+) -> Vec<&'static str> {
+    let mut unprocessed = Vec::new();
+    while let Some(evt) = rx.next().await {
+        if bus.event_loop.stopped.get() {
+            unprocessed.push(evt.type_name);
+            break;
+        }
+
+        let type_name = evt.type_name;
+        let dispatch = pin!(bus.dispatch(evt));
+        match futures::future::select(dispatch, &mut cancel).await {
+            Either::Left((outcomes, _)) => {
+                for outcome in outcomes {
+                    if let Err(e) = outcome.result {
+                        report_error!("Error: {:?}", e);
+                    }
+                }
+            }
+            // dropping the dispatch cancels the in-flight event handlers
+            Either::Right(_) => {
+                unprocessed.push(type_name);
+                break;
+            }
+        }
+    }
+
+    while let Ok(evt) = rx.try_recv() {
+        unprocessed.push(evt.type_name);
+    }
+    unprocessed
+}
+
+impl LocalEvent {
+    fn new<Evt: Clone + 'static>(evt: Evt) -> Self {
+        LocalEvent {
+            type_id: TypeId::of::<Evt>(),
+            type_name: std::any::type_name::<Evt>(),
+            value: Box::new(evt),
+            clone_value: clone_event::<Evt>,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::{LocalBus, LocalHandler, LocalHandlerWrapperTrait, LocalResources};
+use crate::{Error, IntoCommand, IntoEvent, Result};
+
+#[derive(Default)]
+pub struct LocalBusBuilder {
+    req_handlers: HashMap<TypeId, Rc<dyn LocalHandlerWrapperTrait>>,
+    evt_handlers: HashMap<TypeId, Vec<Rc<dyn LocalHandlerWrapperTrait>>>,
+    resources: LocalResources,
+    /// Configuration errors which are returned by `build`
+    errors: Vec<Error>,
+}
+
+impl LocalBusBuilder {
+    pub fn add_req_handler<H, T, Req, Res>(mut self, h: H) -> Self
+    where
+        H: LocalHandler<T, Req, Res> + 'static,
+        T: 'static,
+        Req: IntoCommand<Res>,
+        Res: 'static,
+    {
+        let type_id = TypeId::of::<Req>();
+        let handler = h.into_dyn();
+
+        if let Some(existing) = self.req_handlers.get(&type_id).map(|handler| handler.name()) {
+            self.errors.push(Error::DuplicateHandler {
+                request: std::any::type_name::<Req>(),
+                handlers: vec![existing, handler.name()],
+            });
+            return self;
+        }
+
+        self.req_handlers.insert(type_id, handler);
+
+        self
+    }
+
+    pub fn add_event_handler<H, T, Evt>(mut self, h: H) -> Self
+    where
+        H: LocalHandler<T, Evt, ()> + 'static,
+        T: 'static,
+        Evt: IntoEvent + Clone,
+    {
+        let type_id = TypeId::of::<Evt>();
+        self.evt_handlers.entry(type_id).or_default().push(h.into_dyn());
+
+        self
+    }
+
+    pub fn append_resources<T>(mut self, value: T) -> Self
+    where
+        T: Clone + 'static,
+    {
+        self.resources.insert(value);
+        self
+    }
+
+    /// Build the bus and spawn its event loop on the current tokio `LocalSet`.
+    /// Several handlers for the same request type are reported as `Error::InvalidConfiguration`.
+    pub fn build(self) -> Result<LocalBus> {
+        if !self.errors.is_empty() {
+            return Err(Error::InvalidConfiguration(self.errors));
+        }
+
+        let bus = LocalBus::new(self.resources, self.req_handlers, self.evt_handlers);
+
+        Ok(bus)
+    }
+}
@@ -0,0 +1,109 @@
+use std::any::Any;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use super::LocalResources;
+use crate::{Error, FromResources, IntoHandlerError, Result};
+
+type LocalFuture<T> = Pin<Box<dyn Future<Output = Result<T>>>>;
+
+/// Handler of a `LocalBus`, its resources, request and future do not have to be `Send`
+pub trait LocalHandler<T, Req, Res>: Clone
+where
+    T: 'static,
+    Req: 'static,
+    Res: 'static,
+{
+    type Future: Future<Output = Result<Res>> + 'static;
+
+    fn handle(self, resources: LocalResources, value: Req) -> Self::Future;
+
+    #[allow(private_interfaces)]
+    fn into_dyn(self) -> Rc<dyn LocalHandlerWrapperTrait>
+    where
+        Self: Sized + 'static,
+    {
+        Rc::new(LocalHandlerWrapper {
+            handler: self,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+#[allow(clippy::type_complexity)]
+struct LocalHandlerWrapper<H, T, Req, Res> {
+    handler: H,
+    _phantom: PhantomData<fn() -> (T, Req, Res)>,
+}
+
+pub(crate) trait LocalHandlerWrapperTrait {
+    /// Name of the handler function, used to identify the handler
+    fn name(&self) -> &'static str;
+
+    fn handle(&self, resources: LocalResources, value: Box<dyn Any>) -> LocalFuture<Box<dyn Any>>;
+}
+
+impl<H, T, Req, Res> LocalHandlerWrapperTrait for LocalHandlerWrapper<H, T, Req, Res>
+where
+    H: LocalHandler<T, Req, Res> + 'static,
+    T: 'static,
+    Req: 'static,
+    Res: 'static,
+{
+    fn name(&self) -> &'static str {
+        std::any::type_name::<H>()
+    }
+
+    fn handle(&self, resources: LocalResources, value: Box<dyn Any>) -> LocalFuture<Box<dyn Any>> {
+        let Ok(arg) = value.downcast::<Req>() else {
+            let type_name = std::any::type_name::<Req>();
+            return Box::pin(async { Err(Error::CastError(type_name.to_string())) });
+        };
+
+        let fut = self.handler.clone().handle(resources, *arg);
+        Box::pin(async move {
+            let res = fut.await?;
+            Ok(Box::new(res) as Box<dyn Any>)
+        })
+    }
+}
+
+/// Extract a resource of a local handler, the resource has to be marked with `FromResources`
+fn from_local_resources<T: FromResources + Clone + 'static>(resources: &LocalResources) -> Result<T> {
+    resources.get::<T>().ok_or_else(Error::resource_not_found::<T>)
+}
+
+macro_rules! impl_local_handler {
+    ($($T:ident), *) => {
+        impl<F, Fut, $($T,)* Req, Res, E> LocalHandler<($($T,)*), Req, Res> for F
+        where
+            F: FnOnce($($T,)* Req) -> Fut + Clone + 'static,
+            Req: 'static,
+            Res: 'static,
+            $($T: FromResources + Clone + 'static,)*
+            E: IntoHandlerError,
+            Fut: Future<Output = core::result::Result<Res, E>> + 'static,
+        {
+            type Future = LocalFuture<Res>;
+
+            #[allow(unused)]
+            fn handle(self, resources: LocalResources, value: Req) -> Self::Future {
+                Box::pin(async move {
+                    let res = self($(from_local_resources::<$T>(&resources).map_err(Error::in_handler::<F>)?,)* value).await;
+                    res.map_err(IntoHandlerError::into_handler_error)
+                })
+            }
+        }
+    };
+}
+
+impl_local_handler!();
+impl_local_handler!(T1);
+impl_local_handler!(T1, T2);
+impl_local_handler!(T1, T2, T3);
+impl_local_handler!(T1, T2, T3, T4);
+impl_local_handler!(T1, T2, T3, T4, T5);
+impl_local_handler!(T1, T2, T3, T4, T5, T6);
+impl_local_handler!(T1, T2, T3, T4, T5, T6, T7);
@@ -0,0 +1,19 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Resources of a `LocalBus`, which do not have to be `Send` or `Sync`
+#[derive(Clone, Default)]
+pub struct LocalResources {
+    map: HashMap<TypeId, Rc<dyn Any>>,
+}
+
+impl LocalResources {
+    pub fn get<T: Clone + 'static>(&self) -> Option<T> {
+        self.map.get(&TypeId::of::<T>())?.downcast_ref::<T>().cloned()
+    }
+
+    pub(crate) fn insert<T: Clone + 'static>(&mut self, value: T) {
+        self.map.insert(TypeId::of::<T>(), Rc::new(value));
+    }
+}
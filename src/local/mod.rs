@@ -0,0 +1,11 @@
+mod local_bus;
+mod local_bus_builder;
+mod local_handler;
+mod local_resources;
+
+// -- flatten
+pub use local_bus::LocalBus;
+pub use local_bus_builder::LocalBusBuilder;
+pub use local_handler::LocalHandler;
+pub(crate) use local_handler::LocalHandlerWrapperTrait;
+pub use local_resources::LocalResources;
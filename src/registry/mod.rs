@@ -0,0 +1,57 @@
+use std::any::TypeId;
+
+use crate::BusBuilder;
+
+/// Kind of a declared handler, request and stream handlers are unique per request type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum DeclaredHandlerKind {
+    Request,
+    Stream,
+    Event,
+}
+
+/// Handler declared with `#[medi_handler]`, collected at link time and added with `BusBuilder::add_registered_handlers`
+pub struct DeclaredHandler {
+    /// Path of the handler function
+    pub(crate) name: &'static str,
+    pub(crate) kind: DeclaredHandlerKind,
+    pub(crate) message_type: fn() -> TypeId,
+    pub(crate) message_name: fn() -> &'static str,
+    pub(crate) register: fn(BusBuilder) -> BusBuilder,
+}
+
+#[doc(hidden)]
+impl DeclaredHandler {
+    pub const fn request<Req: 'static>(name: &'static str, register: fn(BusBuilder) -> BusBuilder) -> Self {
+        Self::new::<Req>(name, DeclaredHandlerKind::Request, register)
+    }
+
+    pub const fn stream<Req: 'static>(name: &'static str, register: fn(BusBuilder) -> BusBuilder) -> Self {
+        Self::new::<Req>(name, DeclaredHandlerKind::Stream, register)
+    }
+
+    pub const fn event<Evt: 'static>(name: &'static str, register: fn(BusBuilder) -> BusBuilder) -> Self {
+        Self::new::<Evt>(name, DeclaredHandlerKind::Event, register)
+    }
+
+    const fn new<T: 'static>(
+        name: &'static str,
+        kind: DeclaredHandlerKind,
+        register: fn(BusBuilder) -> BusBuilder,
+    ) -> Self {
+        DeclaredHandler {
+            name,
+            kind,
+            message_type: TypeId::of::<T>,
+            message_name: std::any::type_name::<T>,
+            register,
+        }
+    }
+}
+
+inventory::collect!(DeclaredHandler);
+
+/// All handlers declared with `#[medi_handler]` in the linked crates
+pub(crate) fn declared_handlers() -> impl Iterator<Item = &'static DeclaredHandler> {
+    inventory::iter::<DeclaredHandler>.into_iter()
+}
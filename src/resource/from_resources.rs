@@ -1,3 +1,5 @@
+use std::any::TypeId;
+
 use super::resources::Resources;
 use crate::error::{Error, Result};
 
@@ -6,6 +8,36 @@ pub trait FromResources {
     where
         Self: Sized + Clone + Send + Sync + 'static,
     {
-        resources.get::<Self>().ok_or_else(|| Error::ResourceNotFound)
+        resources.get::<Self>().ok_or_else(Error::resource_not_found::<Self>)
+    }
+
+    /// Resource which has to be appended to the bus, it is validated by `BusBuilder::build`.
+    /// Implementations which do not read a resource of their own type return `None`.
+    fn resource_type() -> Option<ResourceType>
+    where
+        Self: Sized + 'static,
+    {
+        Some(ResourceType::of::<Self>())
+    }
+}
+
+/// Type of a resource which is required by a handler
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResourceType {
+    pub(crate) type_id: TypeId,
+    name: &'static str,
+}
+
+impl ResourceType {
+    pub fn of<T: 'static>() -> Self {
+        ResourceType {
+            type_id: TypeId::of::<T>(),
+            name: std::any::type_name::<T>(),
+        }
+    }
+
+    /// Type name of the resource
+    pub fn name(&self) -> &'static str {
+        self.name
     }
 }
@@ -1,9 +1,11 @@
 mod from_resources;
 mod resources;
+mod resources_builder;
 
 // - flatten
 pub use from_resources::*;
 pub use resources::*;
+pub use resources_builder::*;
 
 use std::{
     any::{Any, TypeId},
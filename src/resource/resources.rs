@@ -1,6 +1,6 @@
 use std::{any::TypeId, sync::Arc};
 
-use super::AnyMap;
+use super::{AnyMap, ResourcesBuilder};
 
 #[derive(Debug, Clone, Default)]
 pub struct Resources {
@@ -8,10 +8,18 @@ pub struct Resources {
 }
 
 impl Resources {
+    pub fn builder() -> ResourcesBuilder {
+        ResourcesBuilder::default()
+    }
+
     pub fn get<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
         self.map.as_ref()?.get(&TypeId::of::<T>())?.downcast_ref::<T>().cloned()
     }
 
+    pub(crate) fn contains(&self, type_id: TypeId) -> bool {
+        self.map.as_ref().is_some_and(|map| map.contains_key(&type_id))
+    }
+
     pub(crate) fn insert<T: Clone + Send + Sync + 'static>(&mut self, value: T) {
         self.map
             .get_or_insert_with(AnyMap::new)
@@ -0,0 +1,19 @@
+use super::Resources;
+
+/// Builder to create a set of resources outside of a bus, e.g. to call a handler with fake dependencies in tests
+#[derive(Debug, Default)]
+pub struct ResourcesBuilder {
+    resources: Resources,
+}
+
+impl ResourcesBuilder {
+    /// Add a resource, an existing resource of the same type is replaced
+    pub fn with<T: Clone + Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.resources.insert(value);
+        self
+    }
+
+    pub fn build(self) -> Resources {
+        self.resources
+    }
+}
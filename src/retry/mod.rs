@@ -0,0 +1,6 @@
+mod retry_handler;
+mod retry_policy;
+
+// -- flatten
+pub(crate) use retry_handler::*;
+pub use retry_policy::*;
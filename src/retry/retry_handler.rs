@@ -0,0 +1,86 @@
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use super::RetryPolicy;
+use crate::handler_wrapper::HandlerWrapperTrait;
+use crate::{IntoCommand, Resources, Result, Runtime};
+
+/// Creates a copy of the type erased request or event for the next attempt
+pub(crate) type CloneValueFn = fn(&(dyn Any + Send + Sync)) -> Option<Box<dyn Any + Send + Sync>>;
+
+pub(crate) fn clone_value<T>(value: &(dyn Any + Send + Sync)) -> Option<Box<dyn Any + Send + Sync>>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let value = value.downcast_ref::<T>()?;
+    Some(Box::new(value.clone()))
+}
+
+/// Copies a request with `IntoCommand::clone_request`, which does not require the request to be `Clone`
+pub(crate) fn clone_request<Req, Res>(value: &(dyn Any + Send + Sync)) -> Option<Box<dyn Any + Send + Sync>>
+where
+    Req: IntoCommand<Res>,
+{
+    let value = value.downcast_ref::<Req>()?;
+    Some(Box::new(value.clone_request()?))
+}
+
+/// Handler decorator which retries the inner handler according to the retry policy
+pub(crate) struct RetryHandler {
+    inner: Arc<dyn HandlerWrapperTrait>,
+    policy: RetryPolicy,
+    clone_value: CloneValueFn,
+    runtime: Runtime,
+}
+
+impl RetryHandler {
+    pub(crate) fn new(
+        inner: Arc<dyn HandlerWrapperTrait>,
+        policy: RetryPolicy,
+        clone_value: CloneValueFn,
+        runtime: Runtime,
+    ) -> Self {
+        RetryHandler {
+            inner,
+            policy,
+            clone_value,
+            runtime,
+        }
+    }
+}
+
+impl HandlerWrapperTrait for RetryHandler {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn handle(
+        &self,
+        resources: Resources,
+        value: Box<dyn Any + Send + Sync>,
+    ) -> Pin<Box<dyn futures::Future<Output = Result<Box<dyn Any + Send + Sync>>> + Send>> {
+        let inner = self.inner.clone();
+        let policy = self.policy.clone();
+        let clone_value = self.clone_value;
+        let runtime = self.runtime.clone();
+
+        Box::pin(async move {
+            let mut attempt = 1;
+            loop {
+                // the inner handler reports the cast error if the value can not be copied
+                let Some(copy) = clone_value(value.as_ref()) else {
+                    return inner.handle(resources, value).await;
+                };
+
+                match inner.handle(resources.clone(), copy).await {
+                    Err(e) if policy.should_retry(attempt, &e) => {
+                        runtime.sleep(policy.delay(attempt)).await;
+                        attempt += 1;
+                    }
+                    res => return res,
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::BuildHasher;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::Error;
+
+type RetryPredicate = Arc<dyn Fn(&Error) -> bool + Send + Sync>;
+
+/// Delay between two attempts of a handler
+#[derive(Debug, Clone, PartialEq)]
+pub enum Backoff {
+    /// Wait the same delay before each retry
+    Fixed(Duration),
+    /// Double the delay before each retry, starting with `initial` and limited by `max`.
+    /// With `jitter` the delay is randomized between the half and the full delay.
+    Exponential {
+        initial: Duration,
+        max: Duration,
+        jitter: bool,
+    },
+}
+
+/// Retry policy for transient failures of request and event handlers
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    backoff: Backoff,
+    retry_on: Option<RetryPredicate>,
+}
+
+impl RetryPolicy {
+    /// Retry up to `max_attempts` attempts in total with the same delay before each retry
+    pub fn fixed(max_attempts: u32, delay: Duration) -> Self {
+        RetryPolicy {
+            max_attempts,
+            backoff: Backoff::Fixed(delay),
+            retry_on: None,
+        }
+    }
+
+    /// Retry up to `max_attempts` attempts in total with an exponential backoff and jitter
+    pub fn exponential(max_attempts: u32, initial: Duration, max: Duration) -> Self {
+        RetryPolicy {
+            max_attempts,
+            backoff: Backoff::Exponential {
+                initial,
+                max,
+                jitter: true,
+            },
+            retry_on: None,
+        }
+    }
+
+    /// Replace the backoff of the policy
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Only retry if the handler error is of type `E` and matches the predicate, see `Error::get_handler_error`
+    pub fn retry_on<E, P>(self, predicate: P) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+        P: Fn(&E) -> bool + Send + Sync + 'static,
+    {
+        self.retry_if(move |error| error.get_handler_error::<E>().is_some_and(&predicate))
+    }
+
+    /// Only retry if the error matches the predicate
+    pub fn retry_if<P>(mut self, predicate: P) -> Self
+    where
+        P: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        self.retry_on = Some(Arc::new(predicate));
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn backoff(&self) -> &Backoff {
+        &self.backoff
+    }
+
+    /// Check if another attempt should be made after the given failed attempt (starting with 1)
+    pub(crate) fn should_retry(&self, attempt: u32, error: &Error) -> bool {
+        if attempt >= self.max_attempts {
+            return false;
+        }
+
+        match &self.retry_on {
+            Some(predicate) => predicate(error),
+            None => true,
+        }
+    }
+
+    /// Delay before the retry after the given failed attempt (starting with 1)
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        match &self.backoff {
+            Backoff::Fixed(delay) => *delay,
+            Backoff::Exponential { initial, max, jitter } => {
+                let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+                let delay = initial.saturating_mul(factor).min(*max);
+                if !jitter {
+                    return delay;
+                }
+
+                let half = delay / 2;
+                let random = RandomState::new().hash_one(attempt);
+                let jitter_nanos = random % (half.as_nanos() as u64).max(1);
+                half + Duration::from_nanos(jitter_nanos)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("backoff", &self.backoff)
+            .field("retry_on", &self.retry_on.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exponential_delay_should_double_until_max() {
+        let policy = RetryPolicy::fixed(5, Duration::ZERO).with_backoff(Backoff::Exponential {
+            initial: Duration::from_millis(10),
+            max: Duration::from_millis(30),
+            jitter: false,
+        });
+
+        assert_eq!(policy.delay(1), Duration::from_millis(10));
+        assert_eq!(policy.delay(2), Duration::from_millis(20));
+        assert_eq!(policy.delay(3), Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_exponential_delay_with_jitter_should_be_between_half_and_full_delay() {
+        let policy = RetryPolicy::exponential(5, Duration::from_millis(10), Duration::from_secs(1));
+
+        for attempt in 1..5 {
+            let delay = policy.delay(attempt);
+            let full = Duration::from_millis(10 * 2u64.pow(attempt - 1));
+            assert!(delay >= full / 2 && delay <= full);
+        }
+    }
+
+    #[test]
+    fn test_should_retry_should_stop_at_max_attempts() {
+        let policy = RetryPolicy::fixed(3, Duration::ZERO);
+
+        let error = Error::HandlerNotFound { request: "GetUser" };
+        assert!(policy.should_retry(2, &error));
+        assert!(!policy.should_retry(3, &error));
+    }
+}
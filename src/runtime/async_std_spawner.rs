@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+
+use super::Spawner;
+
+/// Spawner which runs the tasks on the global async-std executor
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsyncStdSpawner;
+
+impl Spawner for AsyncStdSpawner {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        ::async_std::task::spawn(future);
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(::async_std::task::sleep(duration))
+    }
+
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send>) {
+        ::async_std::task::spawn_blocking(f);
+    }
+}
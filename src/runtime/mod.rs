@@ -0,0 +1,119 @@
+#[cfg(feature = "async-std")]
+mod async_std_spawner;
+#[cfg(feature = "smol")]
+mod smol_spawner;
+mod task;
+#[cfg(feature = "tokio")]
+mod tokio_spawner;
+
+// -- flatten
+#[cfg(feature = "async-std")]
+pub use async_std_spawner::AsyncStdSpawner;
+#[cfg(feature = "smol")]
+pub use smol_spawner::SmolSpawner;
+pub(crate) use task::*;
+#[cfg(feature = "tokio")]
+pub use tokio_spawner::TokioSpawner;
+
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::FutureExt;
+use futures::channel::oneshot;
+use futures::future::{BoxFuture, Either};
+
+/// Spawner trait will be used to run the event loop and the event handlers on the async runtime of the application.
+/// Implementations for tokio, smol and async-std are available with the cargo feature of the same name.
+pub trait Spawner: Send + Sync + 'static {
+    /// Run the future in the background, the bus does not keep the join handle of the runtime
+    fn spawn(&self, future: BoxFuture<'static, ()>);
+
+    /// Future which completes after the duration, used for timeouts and the backoff of retries
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+
+    /// Run the blocking function on a thread where blocking is allowed, used for blocking handlers.
+    /// By default a new thread is started for each call.
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send>) {
+        std::thread::spawn(f);
+    }
+}
+
+/// Spawner of the bus with the task and timer helpers which are built on top of it
+#[derive(Clone)]
+pub(crate) struct Runtime {
+    spawner: Arc<dyn Spawner>,
+}
+
+impl Runtime {
+    pub(crate) fn new(spawner: Arc<dyn Spawner>) -> Self {
+        Runtime { spawner }
+    }
+
+    /// Spawn the future as a task, dropping the returned handle cancels the task.
+    /// A panic of the task is caught and returned as error by the handle.
+    pub(crate) fn spawn<F>(&self, future: F) -> TaskHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let (task, handle) = task(future);
+        self.spawner.spawn(task.boxed());
+        handle
+    }
+
+    /// Run the blocking function with the spawner and wait for its output, a panic is returned as error
+    pub(crate) fn spawn_blocking<F, T>(&self, f: F) -> impl Future<Output = std::thread::Result<T>> + Send + 'static
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        self.spawner.spawn_blocking(Box::new(move || {
+            let _ = tx.send(std::panic::catch_unwind(AssertUnwindSafe(f)));
+        }));
+
+        rx.map(|output| output.unwrap_or_else(|canceled| Err(Box::new(canceled))))
+    }
+
+    pub(crate) fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        self.spawner.sleep(duration)
+    }
+
+    /// Wait for the future until the duration passed, returns `None` and cancels the future on timeout
+    pub(crate) async fn timeout<F>(&self, duration: Duration, future: F) -> Option<F::Output>
+    where
+        F: Future,
+    {
+        match futures::future::select(pin!(future), self.sleep(duration)).await {
+            Either::Left((output, _)) => Some(output),
+            Either::Right(_) => None,
+        }
+    }
+}
+
+/// Spawner of the enabled runtime feature, tokio is preferred over smol and async-std
+#[cfg(feature = "tokio")]
+pub(crate) fn default_spawner() -> Option<Arc<dyn Spawner>> {
+    Some(Arc::new(TokioSpawner::new()))
+}
+
+/// Spawner of the enabled runtime feature, tokio is preferred over smol and async-std
+#[cfg(all(feature = "smol", not(feature = "tokio")))]
+pub(crate) fn default_spawner() -> Option<Arc<dyn Spawner>> {
+    Some(Arc::new(SmolSpawner))
+}
+
+/// Spawner of the enabled runtime feature, tokio is preferred over smol and async-std
+#[cfg(all(feature = "async-std", not(any(feature = "tokio", feature = "smol"))))]
+pub(crate) fn default_spawner() -> Option<Arc<dyn Spawner>> {
+    Some(Arc::new(AsyncStdSpawner))
+}
+
+/// No runtime feature is enabled, the spawner has to be set with `BusBuilder::spawner`
+#[cfg(not(any(feature = "tokio", feature = "smol", feature = "async-std")))]
+pub(crate) fn default_spawner() -> Option<Arc<dyn Spawner>> {
+    None
+}
@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+
+use super::Spawner;
+
+/// Spawner which runs the tasks on the global smol executor
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SmolSpawner;
+
+impl Spawner for SmolSpawner {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        ::smol::spawn(future).detach();
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(async move {
+            ::smol::Timer::after(duration).await;
+        })
+    }
+
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send>) {
+        ::smol::unblock(f).detach();
+    }
+}
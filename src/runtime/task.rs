@@ -0,0 +1,45 @@
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+
+use futures::FutureExt;
+use futures::channel::oneshot;
+use futures::future::{AbortHandle, Abortable};
+
+/// Split the future into a task which can be run by any executor and the handle to wait for its output.
+/// A panic of the future is caught and returned by the handle.
+pub(crate) fn task<F>(future: F) -> (impl Future<Output = ()> + Send + 'static, TaskHandle<F::Output>)
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    let (abort, registration) = AbortHandle::new_pair();
+    let task = Abortable::new(
+        async move {
+            let output = AssertUnwindSafe(future).catch_unwind().await;
+            let _ = tx.send(output);
+        },
+        registration,
+    );
+
+    (task.map(|_| ()), TaskHandle { rx, abort })
+}
+
+/// Handle of a task, dropping it cancels the task
+pub(crate) struct TaskHandle<T> {
+    rx: oneshot::Receiver<std::thread::Result<T>>,
+    abort: AbortHandle,
+}
+
+impl<T> TaskHandle<T> {
+    /// Wait for the output of the task, returns `None` if the task was dropped before it completed
+    pub(crate) async fn join(&mut self) -> Option<std::thread::Result<T>> {
+        (&mut self.rx).await.ok()
+    }
+}
+
+impl<T> Drop for TaskHandle<T> {
+    fn drop(&mut self) {
+        self.abort.abort();
+    }
+}
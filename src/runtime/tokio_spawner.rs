@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use ::tokio::runtime::Handle;
+use futures::future::BoxFuture;
+
+use super::Spawner;
+
+/// Spawner which runs the tasks on a tokio runtime.
+/// The runtime the spawner was created in is used, so the bus can be called from threads outside of it.
+/// If it was created outside of a runtime, the tasks are spawned on the current runtime of the caller.
+#[derive(Debug, Clone)]
+pub struct TokioSpawner {
+    handle: Option<Handle>,
+}
+
+impl TokioSpawner {
+    pub fn new() -> Self {
+        TokioSpawner {
+            handle: Handle::try_current().ok(),
+        }
+    }
+
+    /// Spawner which runs the tasks on the runtime of the handle
+    pub fn with_handle(handle: Handle) -> Self {
+        TokioSpawner { handle: Some(handle) }
+    }
+}
+
+impl Default for TokioSpawner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Spawner for TokioSpawner {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        match &self.handle {
+            Some(handle) => drop(handle.spawn(future)),
+            None => drop(::tokio::spawn(future)),
+        }
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        match &self.handle {
+            Some(handle) => {
+                let _guard = handle.enter();
+                Box::pin(::tokio::time::sleep(duration))
+            }
+            None => Box::pin(::tokio::time::sleep(duration)),
+        }
+    }
+
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send>) {
+        match &self.handle {
+            Some(handle) => drop(handle.spawn_blocking(f)),
+            None => drop(::tokio::task::spawn_blocking(f)),
+        }
+    }
+}
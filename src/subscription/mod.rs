@@ -0,0 +1,177 @@
+mod subscribers;
+
+// -- flatten
+pub(crate) use subscribers::*;
+
+use std::any::TypeId;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use crate::{Event, IntoEvent};
+
+/// What happens with a new event if the buffer of a subscription is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LagPolicy {
+    /// Drop the oldest buffered event to make room for the new one
+    #[default]
+    DropOldest,
+    /// Keep the buffered events and drop the new one
+    DropNewest,
+}
+
+/// Configuration of a subscription, see `Bus::subscribe_with`
+#[derive(Debug, Clone)]
+pub struct SubscriptionConfig {
+    pub(crate) capacity: usize,
+    pub(crate) lag_policy: LagPolicy,
+}
+
+impl SubscriptionConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum number of events which are buffered until the subscriber consumes them
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn lag_policy(mut self, lag_policy: LagPolicy) -> Self {
+        self.lag_policy = lag_policy;
+        self
+    }
+}
+
+impl Default for SubscriptionConfig {
+    fn default() -> Self {
+        SubscriptionConfig {
+            capacity: 64,
+            lag_policy: LagPolicy::default(),
+        }
+    }
+}
+
+/// Stream of the events of type `Evt` which are published after the subscription was created.
+/// The stream ends when the bus is shut down, dropping it ends the subscription.
+pub struct Subscription<Evt> {
+    id: u64,
+    type_id: TypeId,
+    queue: Arc<SubscriptionQueue<Evt>>,
+    subscribers: Subscribers,
+}
+
+impl<Evt> Subscription<Evt>
+where
+    Evt: IntoEvent + Clone,
+{
+    pub(crate) fn new(subscribers: &Subscribers, config: SubscriptionConfig) -> Self {
+        let queue = Arc::new(SubscriptionQueue::new(config));
+        let type_id = TypeId::of::<Evt>();
+        let id = subscribers.add(type_id, queue.clone());
+
+        Subscription {
+            id,
+            type_id,
+            queue,
+            subscribers: subscribers.clone(),
+        }
+    }
+
+    /// Number of events which were dropped because the buffer was full
+    pub fn lagged(&self) -> u64 {
+        self.queue.state.lock().unwrap().lagged
+    }
+}
+
+impl<Evt> futures::Stream for Subscription<Evt> {
+    type Item = Evt;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.queue.state.lock().unwrap();
+        if let Some(evt) = state.events.pop_front() {
+            return Poll::Ready(Some(evt));
+        }
+        if state.closed {
+            return Poll::Ready(None);
+        }
+
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<Evt> Drop for Subscription<Evt> {
+    fn drop(&mut self) {
+        self.subscribers.remove(self.type_id, self.id);
+    }
+}
+
+/// Buffer between the event loop and a subscription
+struct SubscriptionQueue<Evt> {
+    config: SubscriptionConfig,
+    state: Mutex<QueueState<Evt>>,
+}
+
+struct QueueState<Evt> {
+    events: VecDeque<Evt>,
+    waker: Option<Waker>,
+    closed: bool,
+    lagged: u64,
+}
+
+impl<Evt> SubscriptionQueue<Evt> {
+    fn new(config: SubscriptionConfig) -> Self {
+        SubscriptionQueue {
+            state: Mutex::new(QueueState {
+                events: VecDeque::with_capacity(config.capacity),
+                waker: None,
+                closed: false,
+                lagged: 0,
+            }),
+            config,
+        }
+    }
+}
+
+impl<Evt> Subscriber for SubscriptionQueue<Evt>
+where
+    Evt: IntoEvent + Clone,
+{
+    fn deliver(&self, evt: &Event) {
+        let Some(evt) = evt.downcast_ref::<Evt>() else {
+            return;
+        };
+
+        let mut state = self.state.lock().unwrap();
+        if state.events.len() >= self.config.capacity {
+            state.lagged += 1;
+            match self.config.lag_policy {
+                LagPolicy::DropOldest => {
+                    state.events.pop_front();
+                }
+                LagPolicy::DropNewest => return,
+            }
+        }
+        state.events.push_back(evt.clone());
+
+        let waker = state.waker.take();
+        drop(state);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    fn close(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.closed = true;
+
+        let waker = state.waker.take();
+        drop(state);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::Event;
+
+/// Receiver of the published events of one type
+pub(crate) trait Subscriber: Send + Sync {
+    fn deliver(&self, evt: &Event);
+
+    /// No more events will be delivered
+    fn close(&self);
+}
+
+type SubscriberList = Vec<(u64, Arc<dyn Subscriber>)>;
+
+/// Registry of the subscriptions of a bus, shared between all of its clones
+#[derive(Clone, Default)]
+pub(crate) struct Subscribers {
+    inner: Arc<Mutex<SubscribersInner>>,
+}
+
+#[derive(Default)]
+struct SubscribersInner {
+    next_id: u64,
+    closed: bool,
+    by_type: HashMap<TypeId, SubscriberList>,
+}
+
+impl Subscribers {
+    /// Register a subscriber and return its id, the subscriber is closed right away if the bus is shut down
+    pub(crate) fn add(&self, type_id: TypeId, subscriber: Arc<dyn Subscriber>) -> u64 {
+        let mut inner = self.inner.lock().unwrap();
+        inner.next_id += 1;
+        let id = inner.next_id;
+
+        if inner.closed {
+            subscriber.close();
+        } else {
+            inner.by_type.entry(type_id).or_default().push((id, subscriber));
+        }
+
+        id
+    }
+
+    pub(crate) fn remove(&self, type_id: TypeId, id: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(subscribers) = inner.by_type.get_mut(&type_id) {
+            subscribers.retain(|(subscriber_id, _)| *subscriber_id != id);
+            if subscribers.is_empty() {
+                inner.by_type.remove(&type_id);
+            }
+        }
+    }
+
+    /// Whether there is a subscriber for events of the type
+    pub(crate) fn contains(&self, type_id: &TypeId) -> bool {
+        self.inner.lock().unwrap().by_type.contains_key(type_id)
+    }
+
+    /// Deliver the event to all subscribers of its type, returns false if there is no subscriber
+    pub(crate) fn deliver(&self, evt: &Event) -> bool {
+        let subscribers: Vec<Arc<dyn Subscriber>> = {
+            let inner = self.inner.lock().unwrap();
+            let Some(subscribers) = inner.by_type.get(&evt.type_id()) else {
+                return false;
+            };
+            subscribers.iter().map(|(_, subscriber)| subscriber.clone()).collect()
+        };
+
+        for subscriber in &subscribers {
+            subscriber.deliver(evt);
+        }
+
+        true
+    }
+
+    /// Close all subscribers, their streams end after the buffered events
+    pub(crate) fn close(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.closed = true;
+        for (_, subscriber) in inner.by_type.drain().flat_map(|(_, subscribers)| subscribers) {
+            subscriber.close();
+        }
+    }
+}
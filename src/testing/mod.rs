@@ -0,0 +1,5 @@
+//! Test helpers for code which uses the bus, only available with the `testing` feature
+mod recording_bus;
+
+// -- flatten
+pub use recording_bus::*;
@@ -0,0 +1,196 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::RecordedEvents;
+use crate::{Behavior, BehaviorFuture, Bus, Error, Event, IntoCommand, IntoEvent, Next, Request, Response, Result};
+
+type Stub = Arc<dyn Fn(&Request) -> Result<Response> + Send + Sync>;
+
+/// Bus for tests which records every sent request and published event instead of handling them.
+///
+/// The responses of the requests are stubbed per request type with `RecordingBus::stub`,
+/// a request without stub fails with `Error::HandlerNotFound`.
+/// The published events are recorded when they are published, also with `Bus::publish_and_wait`,
+/// and are not processed by any handler.
+#[derive(Clone)]
+pub struct RecordingBus {
+    bus: Bus,
+    state: Arc<RecordingState>,
+}
+
+#[derive(Default)]
+struct RecordingState {
+    stubs: Mutex<HashMap<TypeId, Stub>>,
+    sent: Mutex<Vec<Request>>,
+    published: RecordedEvents,
+}
+
+impl RecordingBus {
+    /// Create a recording bus with the spawner of the enabled runtime feature
+    ///
+    /// # Panics
+    ///
+    /// Panics if none of the runtime features `tokio`, `smol` or `async-std` is enabled.
+    pub fn new() -> Self {
+        let state = Arc::new(RecordingState::default());
+        let bus = Bus::builder()
+            .add_behavior(RecordingBehavior(state.clone()))
+            .record_events(state.published.clone())
+            .manual_event_processing()
+            .build()
+            .expect("recording bus needs a runtime feature for its spawner");
+
+        RecordingBus { bus, state }
+    }
+
+    /// The bus to pass to the code under test, e.g. as resource of a handler
+    pub fn bus(&self) -> Bus {
+        self.bus.clone()
+    }
+
+    /// Respond to requests of type `Req` with the result of the stub
+    pub fn stub<Req, Res, F>(&self, stub: F) -> &Self
+    where
+        Req: IntoCommand<Res>,
+        Res: Send + Sync + 'static,
+        F: Fn(&Req) -> Result<Res> + Send + Sync + 'static,
+    {
+        let stub: Stub = Arc::new(move |req| {
+            let Some(req) = req.downcast_ref::<Req>() else {
+                let type_name = std::any::type_name::<Req>();
+                return Err(Error::CastError(type_name.to_string()));
+            };
+            stub(req).map(|res| Box::new(res) as Response)
+        });
+        self.state.stubs.lock().unwrap().insert(TypeId::of::<Req>(), stub);
+        self
+    }
+
+    /// Copies of the sent requests of type `Req` in the order they were sent
+    pub fn sent<Req>(&self) -> Vec<Req>
+    where
+        Req: Clone + 'static,
+    {
+        let sent = self.state.sent.lock().unwrap();
+        sent.iter()
+            .filter_map(|req| req.downcast_ref::<Req>())
+            .cloned()
+            .collect()
+    }
+
+    /// Copies of the published events of type `Evt` in the order they were published
+    pub fn published<Evt>(&self) -> Vec<Evt>
+    where
+        Evt: IntoEvent + Clone,
+    {
+        let published = self.state.published.lock().unwrap();
+        published
+            .iter()
+            .filter_map(|evt| evt.downcast_ref::<Evt>())
+            .cloned()
+            .collect()
+    }
+
+    /// Assert that a request of type `Req` matching the predicate was sent
+    #[track_caller]
+    pub fn assert_sent<Req, P>(&self, predicate: P)
+    where
+        Req: 'static,
+        P: Fn(&Req) -> bool,
+    {
+        let sent = self.state.sent.lock().unwrap();
+        let found = sent.iter().filter_map(|req| req.downcast_ref::<Req>()).any(predicate);
+        if !found {
+            let type_names: Vec<&str> = sent.iter().map(Request::type_name).collect();
+            panic!(
+                "No matching request '{}' was sent, sent requests: {:?}",
+                std::any::type_name::<Req>(),
+                type_names
+            );
+        }
+    }
+
+    /// Assert that an event of type `Evt` matching the predicate was published
+    #[track_caller]
+    pub fn assert_published<Evt, P>(&self, predicate: P)
+    where
+        Evt: IntoEvent,
+        P: Fn(&Evt) -> bool,
+    {
+        let published = self.state.published.lock().unwrap();
+        let found = published
+            .iter()
+            .filter_map(|evt| evt.downcast_ref::<Evt>())
+            .any(predicate);
+        if !found {
+            let type_names: Vec<&str> = published.iter().map(Event::type_name).collect();
+            panic!(
+                "No matching event '{}' was published, published events: {:?}",
+                std::any::type_name::<Evt>(),
+                type_names
+            );
+        }
+    }
+
+    /// Assert that no event of type `Evt` was published
+    #[track_caller]
+    pub fn assert_not_published<Evt>(&self)
+    where
+        Evt: IntoEvent,
+    {
+        let published = self.published_count::<Evt>();
+        if published > 0 {
+            panic!(
+                "Event '{}' was published {} time(s)",
+                std::any::type_name::<Evt>(),
+                published
+            );
+        }
+    }
+
+    /// Number of sent requests of type `Req`
+    pub fn sent_count<Req>(&self) -> usize
+    where
+        Req: 'static,
+    {
+        let sent = self.state.sent.lock().unwrap();
+        sent.iter().filter(|req| req.type_id() == TypeId::of::<Req>()).count()
+    }
+
+    /// Number of published events of type `Evt`
+    pub fn published_count<Evt>(&self) -> usize
+    where
+        Evt: IntoEvent,
+    {
+        let published = self.state.published.lock().unwrap();
+        published
+            .iter()
+            .filter(|evt| evt.type_id() == TypeId::of::<Evt>())
+            .count()
+    }
+}
+
+impl Default for RecordingBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Records every request and responds with its stub instead of calling a handler
+struct RecordingBehavior(Arc<RecordingState>);
+
+impl Behavior for RecordingBehavior {
+    fn handle<'a>(&'a self, req: Request, _next: Next<'a>) -> BehaviorFuture<'a> {
+        let stub = self.0.stubs.lock().unwrap().get(&req.type_id()).cloned();
+        let res = match stub {
+            Some(stub) => stub(&req),
+            None => Err(Error::HandlerNotFound {
+                request: req.type_name(),
+            }),
+        };
+        self.0.sent.lock().unwrap().push(req);
+
+        Box::pin(async move { res })
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
-use medi_rs::{Bus, FromResources, IntoCommand};
-use medi_rs_macros::{MediCommand, MediRessource};
+use medi_rs::{Bus, FromResources, IntoCommand, IntoEvent};
+use medi_rs_macros::{MediCommand, MediEvent, MediRessource};
 use tokio::sync::Mutex;
 
 #[tokio::test]
@@ -34,28 +34,56 @@ async fn send_should_return_error_when_handler_not_found() {
     match res {
         Ok(_) => panic!("Expected error, got {:?}", res),
         Err(err) => match err {
-            medi_rs::Error::HandlerNotFound => (),
+            medi_rs::Error::HandlerNotFound { request } => assert!(request.ends_with("BasicRequest")),
             _ => panic!("Expected HandlerNotFound, got {:?}", err),
         },
     }
 }
 
 #[tokio::test]
-async fn send_should_return_error_when_no_resource_found() {
-    let bus = Bus::builder().add_req_handler(error_handler1).build().unwrap();
-
-    let res = bus.send(BasicRequest).await;
+async fn build_should_return_error_when_no_resource_found() {
+    let res = Bus::builder().add_req_handler(error_handler1).build();
 
     // assert
     match res {
-        Ok(_) => panic!("Expected error, got {:?}", res),
-        Err(err) => match err {
-            medi_rs::Error::ResourceNotFound => (),
-            _ => panic!("Expected ResourceNotFound, got {:?}", err),
+        Ok(_) => panic!("Expected error, got a bus"),
+        Err(medi_rs::Error::InvalidConfiguration(errors)) => match errors.as_slice() {
+            [
+                medi_rs::Error::MissingResource {
+                    handler,
+                    request,
+                    resource,
+                },
+            ] => {
+                assert!(handler.ends_with("error_handler1"));
+                assert!(request.ends_with("BasicRequest"));
+                assert!(resource.ends_with("AppState"));
+            }
+            _ => panic!("Expected MissingResource, got {:?}", errors),
         },
+        Err(err) => panic!("Expected InvalidConfiguration, got {:?}", err),
     }
 }
 
+#[tokio::test]
+async fn build_should_return_error_for_each_missing_resource() {
+    let res = Bus::builder()
+        .add_req_handler(error_handler1)
+        .add_event_handler(basic_event_handler)
+        .build();
+
+    // assert
+    let Err(medi_rs::Error::InvalidConfiguration(errors)) = res else {
+        panic!("Expected InvalidConfiguration");
+    };
+    // the bus itself is always available
+    assert_eq!(errors.len(), 2);
+    let message = medi_rs::Error::InvalidConfiguration(errors).to_string();
+    assert!(message.contains("error_handler1"));
+    assert!(message.contains("basic_event_handler"));
+    assert!(message.contains("BasicEvent"));
+}
+
 async fn error_handler(_req: BasicRequest) -> Result<(), CustomError> {
     Err(CustomError::Basic("Error1".to_string()))
 }
@@ -65,9 +93,16 @@ async fn error_handler1(state: AppState, _req: BasicRequest) -> Result<(), Custo
     Err(CustomError::Basic("Error2".to_string()))
 }
 
+async fn basic_event_handler(_state: AppState, _bus: Bus, _evt: BasicEvent) -> Result<(), CustomError> {
+    Ok(())
+}
+
 #[derive(MediCommand)]
 struct BasicRequest;
 
+#[derive(Clone, MediEvent)]
+struct BasicEvent;
+
 #[derive(Debug, Clone, MediRessource)]
 pub struct AppState {
     pub list: Arc<Mutex<Vec<String>>>,
@@ -14,6 +14,32 @@ async fn send_call_second_req_test() {
     assert!(res.is_ok());
 }
 
+#[tokio::test]
+async fn send_should_return_error_of_nested_send_unchanged() {
+    let bus = Bus::builder().add_req_handler(create_user_dyn).build().unwrap();
+
+    let res = bus.send(CreateUser { name: "hello".into() }).await;
+
+    match res {
+        Err(medi_rs::Error::HandlerNotFound { request }) => assert!(request.ends_with("ValidateUser")),
+        _ => panic!("Expected HandlerNotFound, got {:?}", res),
+    }
+}
+
+#[tokio::test]
+async fn send_should_return_handler_error_of_nested_send() {
+    let bus = Bus::builder()
+        .add_req_handler(reject_user)
+        .add_req_handler(create_user_dyn)
+        .build()
+        .unwrap();
+
+    let res = bus.send(CreateUser { name: "hello".into() }).await;
+
+    let err = res.unwrap_err();
+    assert!(err.get_handler_error::<InvalidUser>().is_some());
+}
+
 #[derive(MediCommand)]
 struct CreateUser {
     name: String,
@@ -31,6 +57,14 @@ async fn create_user_dyn(bus: Bus, req: CreateUser) -> Result<()> {
     Ok(())
 }
 
+async fn reject_user(_req: ValidateUser) -> core::result::Result<(), InvalidUser> {
+    Err(InvalidUser)
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid user")]
+struct InvalidUser;
+
 async fn validate_user(req: ValidateUser) -> Result<()> {
     println!("Validating user: {}", req.name);
     Ok(())
@@ -0,0 +1,142 @@
+use std::sync::{Arc, Mutex};
+
+use medi_rs::{Behavior, BehaviorFuture, Bus, IntoCommand, Next, Request, Response, Result};
+use medi_rs_macros::MediCommand;
+
+#[tokio::test]
+async fn send_should_run_behaviors_in_registration_order() {
+    let log = Log::default();
+    let bus = Bus::builder()
+        .add_req_handler(create_user)
+        .add_req_behavior::<CreateUser, _>(Recording("create_user", log.clone()))
+        .add_behavior(Recording("first", log.clone()))
+        .add_behavior(Recording("second", log.clone()))
+        .build()
+        .unwrap();
+
+    let res = bus.send(CreateUser { name: "John".into() }).await;
+
+    assert_eq!(res.unwrap(), "John");
+    assert_eq!(
+        log.entries(),
+        vec![
+            "first before",
+            "second before",
+            "create_user before",
+            "create_user after",
+            "second after",
+            "first after",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn send_should_run_request_behavior_only_for_its_request_type() {
+    let log = Log::default();
+    let bus = Bus::builder()
+        .add_req_handler(create_user)
+        .add_req_handler(delete_user)
+        .add_req_behavior::<CreateUser, _>(Recording("create_user", log.clone()))
+        .build()
+        .unwrap();
+
+    bus.send(DeleteUser).await.unwrap();
+
+    assert!(log.entries().is_empty());
+}
+
+#[tokio::test]
+async fn send_should_short_circuit_when_behavior_rejects_the_request() {
+    let bus = Bus::builder()
+        .add_req_handler(create_user)
+        .add_req_behavior::<CreateUser, _>(Validation)
+        .build()
+        .unwrap();
+
+    let res = bus.send(CreateUser { name: "".into() }).await;
+
+    let err = res.unwrap_err();
+    assert!(matches!(
+        err.get_handler_error::<ValidationError>(),
+        Some(ValidationError)
+    ));
+}
+
+#[tokio::test]
+async fn send_should_run_behaviors_when_handler_not_found() {
+    let log = Log::default();
+    let bus = Bus::builder()
+        .add_behavior(Recording("global", log.clone()))
+        .build()
+        .unwrap();
+
+    let res = bus.send(DeleteUser).await;
+
+    assert!(res.unwrap_err().is_handler_not_found());
+    assert_eq!(log.entries(), vec!["global before", "global after"]);
+}
+
+#[derive(MediCommand)]
+#[medi_command(return_type = String)]
+struct CreateUser {
+    name: String,
+}
+
+#[derive(MediCommand)]
+struct DeleteUser;
+
+async fn create_user(req: CreateUser) -> Result<String> {
+    Ok(req.name)
+}
+
+async fn delete_user(_req: DeleteUser) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Clone, Default)]
+struct Log(Arc<Mutex<Vec<String>>>);
+
+impl Log {
+    fn push(&self, entry: String) {
+        self.0.lock().unwrap().push(entry);
+    }
+
+    fn entries(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Records when the pipeline enters and leaves the behavior
+struct Recording(&'static str, Log);
+
+impl Behavior for Recording {
+    fn handle<'a>(&'a self, req: Request, next: Next<'a>) -> BehaviorFuture<'a> {
+        Box::pin(async move {
+            self.1.push(format!("{} before", self.0));
+            let res = next.run(req).await;
+            self.1.push(format!("{} after", self.0));
+            res
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Validation failed")]
+struct ValidationError;
+
+/// Rejects users without a name before the handler is called
+struct Validation;
+
+impl Behavior for Validation {
+    fn handle<'a>(&'a self, req: Request, next: Next<'a>) -> BehaviorFuture<'a> {
+        Box::pin(async move {
+            let is_valid = req.downcast_ref::<CreateUser>().is_some_and(|req| !req.name.is_empty());
+            if !is_valid {
+                return Err(medi_rs::Error::Handler(Box::new(ValidationError)));
+            }
+
+            let res: Response = next.run(req).await?;
+            Ok(res)
+        })
+    }
+}
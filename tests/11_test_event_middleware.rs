@@ -0,0 +1,133 @@
+use std::sync::{Arc, Mutex};
+
+use medi_rs::{Bus, Event, EventMiddleware, EventMiddlewareFuture, EventNext, FromResources, IntoEvent, Result};
+use medi_rs_macros::{MediEvent, MediRessource};
+
+#[tokio::test]
+async fn publish_should_pass_enriched_event_to_handlers() {
+    let log = Log::default();
+    let bus = Bus::builder()
+        .add_event_handler(record_tenant)
+        .add_event_middleware(Enrich)
+        .append_resources(log.clone())
+        .build()
+        .unwrap();
+
+    bus.publish(OrderPlaced { tenant: "acme".into() }).await.unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await; // wait for event processing
+
+    assert_eq!(log.entries(), vec!["handler acme-enriched"]);
+}
+
+#[tokio::test]
+async fn publish_should_not_reach_handlers_when_middleware_filters_event() {
+    let log = Log::default();
+    let bus = Bus::builder()
+        .add_event_handler(record_tenant)
+        .add_event_middleware(TenantFilter("acme"))
+        .append_resources(log.clone())
+        .build()
+        .unwrap();
+
+    bus.publish(OrderPlaced { tenant: "other".into() }).await.unwrap();
+    bus.publish(OrderPlaced { tenant: "acme".into() }).await.unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await; // wait for event processing
+
+    assert_eq!(log.entries(), vec!["handler acme"]);
+}
+
+#[tokio::test]
+async fn publish_should_report_handler_outcomes_to_middleware() {
+    let log = Log::default();
+    let bus = Bus::builder()
+        .add_event_handler(record_tenant)
+        .add_event_handler(failing_handler)
+        .add_event_middleware(Audit(log.clone()))
+        .append_resources(log.clone())
+        .build()
+        .unwrap();
+
+    bus.publish(OrderPlaced { tenant: "acme".into() }).await.unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await; // wait for event processing
+
+    let entries = log.entries();
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[0], "audit before OrderPlaced");
+    assert_eq!(entries[1], "handler acme");
+    assert!(entries.contains(&"audit record_tenant ok".to_string()));
+    assert!(entries.contains(&"audit failing_handler failed".to_string()));
+}
+
+#[derive(Clone, MediEvent)]
+struct OrderPlaced {
+    tenant: String,
+}
+
+#[derive(Clone, Default, MediRessource)]
+struct Log(Arc<Mutex<Vec<String>>>);
+
+impl Log {
+    fn push(&self, entry: String) {
+        self.0.lock().unwrap().push(entry);
+    }
+
+    fn entries(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+async fn record_tenant(log: Log, evt: OrderPlaced) -> Result<()> {
+    log.push(format!("handler {}", evt.tenant));
+    Ok(())
+}
+
+async fn failing_handler(_evt: OrderPlaced) -> Result<()> {
+    Err(medi_rs::Error::NoEventHandlerRegistered)
+}
+
+/// Appends a suffix to the tenant of each order
+struct Enrich;
+
+impl EventMiddleware for Enrich {
+    fn handle<'a>(&'a self, mut evt: Event, next: EventNext<'a>) -> EventMiddlewareFuture<'a> {
+        if let Some(order) = evt.downcast_mut::<OrderPlaced>() {
+            order.tenant.push_str("-enriched");
+        }
+        next.run(evt)
+    }
+}
+
+/// Only lets orders of a single tenant pass
+struct TenantFilter(&'static str);
+
+impl EventMiddleware for TenantFilter {
+    fn handle<'a>(&'a self, evt: Event, next: EventNext<'a>) -> EventMiddlewareFuture<'a> {
+        let allowed = evt
+            .downcast_ref::<OrderPlaced>()
+            .is_some_and(|order| order.tenant == self.0);
+        if !allowed {
+            return Box::pin(async { Vec::new() });
+        }
+        next.run(evt)
+    }
+}
+
+/// Records the event type and the outcome of each handler
+struct Audit(Log);
+
+impl EventMiddleware for Audit {
+    fn handle<'a>(&'a self, evt: Event, next: EventNext<'a>) -> EventMiddlewareFuture<'a> {
+        Box::pin(async move {
+            let type_name = evt.type_name().rsplit("::").next().unwrap_or_default();
+            self.0.push(format!("audit before {}", type_name));
+
+            let outcomes = next.run(evt).await;
+            for outcome in &outcomes {
+                let handler = outcome.handler.rsplit("::").next().unwrap_or_default();
+                let status = if outcome.result.is_ok() { "ok" } else { "failed" };
+                self.0.push(format!("audit {} {}", handler, status));
+            }
+            outcomes
+        })
+    }
+}
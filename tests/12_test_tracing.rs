@@ -0,0 +1,117 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+
+use medi_rs::{Bus, IntoCommand, IntoEvent, Result};
+use medi_rs_macros::{MediCommand, MediEvent};
+use tracing::{
+    Id, Subscriber,
+    field::{Field, Visit},
+    span::Attributes,
+};
+use tracing_subscriber::{
+    Registry,
+    layer::{Context, Layer, SubscriberExt},
+    registry::LookupSpan,
+};
+
+#[tokio::test]
+async fn send_should_create_span_with_event_handler_spans_as_children() {
+    let recorder = SpanRecorder::default();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(recorder.clone()));
+    let bus = Bus::builder()
+        .add_req_handler(create_user)
+        .add_event_handler(user_created)
+        .build()
+        .unwrap();
+
+    bus.send(CreateUser).await.unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await; // wait for event processing
+
+    let spans = recorder.spans();
+    let send_span = spans.iter().find(|span| span.name == "medi.send").unwrap();
+    assert!(
+        send_span
+            .fields
+            .contains(&("request".into(), std::any::type_name::<CreateUser>().into()))
+    );
+
+    let handler_span = spans.iter().find(|span| span.name == "medi.event_handler").unwrap();
+    assert_eq!(handler_span.parent, Some(send_span.id));
+    assert!(
+        handler_span
+            .fields
+            .contains(&("event".into(), std::any::type_name::<UserCreated>().into()))
+    );
+    assert!(
+        handler_span
+            .fields
+            .iter()
+            .any(|(k, v)| k == "handler" && v.ends_with("user_created"))
+    );
+}
+
+#[derive(MediCommand)]
+struct CreateUser;
+
+#[derive(Clone, MediEvent)]
+struct UserCreated;
+
+async fn create_user(bus: Bus, _req: CreateUser) -> Result<()> {
+    bus.publish(UserCreated).await
+}
+
+async fn user_created(_evt: UserCreated) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+struct RecordedSpan {
+    id: u64,
+    name: &'static str,
+    parent: Option<u64>,
+    fields: Vec<(String, String)>,
+}
+
+/// Layer which records all created spans with their parent
+#[derive(Clone, Default)]
+struct SpanRecorder(Arc<Mutex<Vec<RecordedSpan>>>);
+
+impl SpanRecorder {
+    fn spans(&self) -> Vec<RecordedSpan> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().into(), value.into()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name().into(), format!("{:?}", value)));
+    }
+}
+
+impl<S> Layer<S> for SpanRecorder
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let parent = ctx
+            .span(id)
+            .and_then(|span| span.parent())
+            .map(|parent| parent.id().into_u64());
+
+        let mut fields = Vec::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        self.0.lock().unwrap().push(RecordedSpan {
+            id: id.into_u64(),
+            name: attrs.metadata().name(),
+            parent,
+            fields,
+        });
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+use std::time::Duration;
+
+use medi_rs::{Bus, FromResources, IntoEvent, Result};
+use medi_rs_macros::{MediEvent, MediRessource};
+
+#[tokio::test]
+async fn drain_should_process_all_queued_events() {
+    let counter = Counter::default();
+    let bus = Bus::builder()
+        .add_event_handler(slow_handler)
+        .append_resources(counter.clone())
+        .build()
+        .unwrap();
+
+    for _ in 0..3 {
+        bus.publish(SlowEvent(10)).await.unwrap();
+    }
+    let report = bus.drain(None).await;
+
+    assert_eq!(counter.get(), 3);
+    assert!(report.unprocessed_events.is_empty());
+    assert!(!report.deadline_exceeded);
+}
+
+#[tokio::test]
+async fn shutdown_should_leave_queued_events_unprocessed() {
+    let counter = Counter::default();
+    let bus = Bus::builder()
+        .add_event_handler(slow_handler)
+        .append_resources(counter.clone())
+        .build()
+        .unwrap();
+
+    for _ in 0..3 {
+        bus.publish(SlowEvent(100)).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(20)).await; // wait until the first event is in-flight
+    let report = bus.shutdown(None).await;
+
+    assert_eq!(counter.get(), 1);
+    assert_eq!(report.unprocessed_events.len(), 2);
+    assert!(report.unprocessed_events[0].ends_with("SlowEvent"));
+    assert!(!report.deadline_exceeded);
+}
+
+#[tokio::test]
+async fn shutdown_should_cancel_in_flight_handlers_when_deadline_passes() {
+    let counter = Counter::default();
+    let bus = Bus::builder()
+        .add_event_handler(slow_handler)
+        .append_resources(counter.clone())
+        .build()
+        .unwrap();
+
+    bus.publish(SlowEvent(10_000)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(20)).await; // wait until the event is in-flight
+    let watch = std::time::Instant::now();
+    let report = bus.shutdown(Some(Duration::from_millis(50))).await;
+
+    assert!(watch.elapsed() < Duration::from_secs(1));
+    assert_eq!(counter.get(), 0);
+    assert_eq!(report.unprocessed_events.len(), 1);
+    assert!(report.deadline_exceeded);
+}
+
+#[tokio::test]
+async fn publish_should_return_error_after_shutdown() {
+    let bus = Bus::builder()
+        .add_event_handler(slow_handler)
+        .append_resources(Counter::default())
+        .build()
+        .unwrap();
+
+    bus.shutdown(None).await;
+    let res = bus.publish(SlowEvent(0)).await;
+
+    assert!(matches!(res, Err(medi_rs::Error::EventPublishingError)));
+}
+
+/// Event which takes the given milliseconds to be processed
+#[derive(Clone, MediEvent)]
+struct SlowEvent(u64);
+
+#[derive(Clone, Default, MediRessource)]
+struct Counter(Arc<AtomicUsize>);
+
+impl Counter {
+    fn get(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+async fn slow_handler(counter: Counter, evt: SlowEvent) -> Result<()> {
+    tokio::time::sleep(Duration::from_millis(evt.0)).await;
+    counter.0.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::sync::{Arc, Mutex};
+
+use futures::{FutureExt, StreamExt};
+use medi_rs::{Bus, FromResources, IntoEvent, Result};
+use medi_rs_macros::{MediEvent, MediRessource};
+
+#[tokio::test]
+async fn publish_and_wait_should_complete_all_handlers_before_returning() {
+    let queue = InMemoryMsgQueue::default();
+    let bus = Bus::builder()
+        .add_event_handler(send_mail)
+        .add_event_handler(update_stats)
+        .append_resources(queue.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.publish_and_wait(UserCreated { fail: false }).await;
+
+    assert!(res.is_ok());
+    let mut messages = queue.0.lock().unwrap().clone();
+    messages.sort();
+    assert_eq!(messages, vec!["send_mail", "update_stats"]);
+}
+
+#[tokio::test]
+async fn publish_and_wait_should_return_failed_handlers() {
+    let queue = InMemoryMsgQueue::default();
+    let bus = Bus::builder()
+        .add_event_handler(send_mail)
+        .add_event_handler(update_stats)
+        .append_resources(queue.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.publish_and_wait(UserCreated { fail: true }).await;
+
+    let Err(medi_rs::Error::EventProcessingError(failures)) = res else {
+        panic!("Expected EventProcessingError, got {:?}", res);
+    };
+    assert_eq!(failures.len(), 1);
+    assert!(failures[0].handler.ends_with("send_mail"));
+    assert!(matches!(
+        failures[0].error.get_handler_error::<MailError>(),
+        Some(MailError::Unreachable)
+    ));
+    assert_eq!(queue.0.lock().unwrap().clone(), vec!["update_stats"]);
+}
+
+#[tokio::test]
+async fn publish_and_wait_should_return_error_when_no_handler_registered() {
+    let bus = Bus::builder().build().unwrap();
+
+    let res = bus.publish_and_wait(UserCreated { fail: false }).await;
+
+    assert!(matches!(res, Err(medi_rs::Error::NoEventHandlerRegistered)));
+}
+
+#[tokio::test]
+async fn publish_and_wait_should_deliver_event_to_subscribers_without_handler() {
+    let bus = Bus::builder().build().unwrap();
+    let mut users = bus.subscribe::<UserCreated>();
+
+    let res = bus.publish_and_wait(UserCreated { fail: false }).await;
+
+    assert!(res.is_ok());
+    let user = users.next().now_or_never().flatten();
+    assert!(user.is_some_and(|user| !user.fail));
+}
+
+#[derive(Clone, MediEvent)]
+struct UserCreated {
+    fail: bool,
+}
+
+#[derive(Clone, Default, MediRessource)]
+struct InMemoryMsgQueue(Arc<Mutex<Vec<String>>>);
+
+#[derive(Debug, thiserror::Error)]
+enum MailError {
+    #[error("Mail server unreachable")]
+    Unreachable,
+}
+
+async fn send_mail(queue: InMemoryMsgQueue, evt: UserCreated) -> core::result::Result<(), MailError> {
+    if evt.fail {
+        return Err(MailError::Unreachable);
+    }
+    queue.0.lock().unwrap().push("send_mail".to_string());
+    Ok(())
+}
+
+async fn update_stats(queue: InMemoryMsgQueue, _evt: UserCreated) -> Result<()> {
+    queue.0.lock().unwrap().push("update_stats".to_string());
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use medi_rs::{Bus, DeadLetter, DeadLetterId, DeadLetterStore, FromResources, IntoEvent, Result};
+use medi_rs::{Event, EventMiddleware, EventMiddlewareFuture, EventNext};
+use medi_rs_macros::{MediEvent, MediRessource};
+
+#[tokio::test]
+async fn failed_event_handler_should_create_dead_letter() {
+    let mail = MailServer::default();
+    let bus = Bus::builder()
+        .add_event_handler(send_mail)
+        .add_event_handler(update_stats)
+        .append_resources(mail.clone())
+        .build()
+        .unwrap();
+
+    bus.publish(UserCreated { name: "John".into() }).await.unwrap();
+    bus.drain(None).await;
+
+    let letters = bus.dead_letters();
+    assert_eq!(letters.len(), 1);
+    let letter = bus.dead_letter(letters[0].id).unwrap();
+    assert!(letter.handler.ends_with("send_mail"));
+    assert!(letter.event_type().ends_with("UserCreated"));
+    assert_eq!(letter.event::<UserCreated>().unwrap().name, "John");
+    assert_eq!(letter.attempts, 1);
+    assert!(letter.error.get_handler_error::<MailError>().is_some());
+}
+
+#[tokio::test]
+async fn redeliver_should_only_call_failed_handler_and_remove_dead_letter() {
+    let mail = MailServer::default();
+    let bus = Bus::builder()
+        .add_event_handler(send_mail)
+        .add_event_handler(update_stats)
+        .append_resources(mail.clone())
+        .build()
+        .unwrap();
+
+    bus.publish(UserCreated { name: "John".into() }).await.unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await; // wait for event processing
+    let id = bus.dead_letters()[0].id;
+
+    mail.online.store(true, Ordering::SeqCst);
+    let res = bus.redeliver(id).await;
+
+    assert!(res.is_ok());
+    assert!(bus.dead_letters().is_empty());
+    assert_eq!(mail.sent.load(Ordering::SeqCst), 1);
+    assert_eq!(mail.stats.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn redeliver_should_keep_dead_letter_with_increased_attempts_when_handler_fails_again() {
+    let mail = MailServer::default();
+    let bus = Bus::builder()
+        .add_event_handler(send_mail)
+        .append_resources(mail.clone())
+        .build()
+        .unwrap();
+
+    bus.publish(UserCreated { name: "John".into() }).await.unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await; // wait for event processing
+    let id = bus.dead_letters()[0].id;
+
+    let res = bus.redeliver(id).await;
+
+    let err = res.unwrap_err();
+    assert!(matches!(err, medi_rs::Error::RedeliveryFailed { .. }));
+    assert!(err.get_handler_error::<MailError>().is_some());
+    assert_eq!(bus.dead_letter(id).unwrap().attempts, 2);
+}
+
+#[tokio::test]
+async fn redeliver_should_return_error_when_dead_letter_not_found() {
+    let bus = Bus::builder().build().unwrap();
+
+    let res = bus.redeliver(DeadLetterId(42)).await;
+
+    assert!(matches!(res, Err(medi_rs::Error::DeadLetterNotFound(DeadLetterId(42)))));
+}
+
+#[tokio::test]
+async fn failed_event_handler_should_use_custom_dead_letter_store() {
+    let store = CountingStore::default();
+    let bus = Bus::builder()
+        .add_event_handler(send_mail)
+        .append_resources(MailServer::default())
+        .dead_letter_store(store.clone())
+        .build()
+        .unwrap();
+
+    bus.publish(UserCreated { name: "John".into() }).await.unwrap();
+    bus.drain(None).await;
+
+    assert_eq!(store.0.load(Ordering::SeqCst), 1);
+    assert!(bus.dead_letters().is_empty());
+}
+
+#[tokio::test]
+async fn dead_letter_should_keep_event_enriched_by_middleware() {
+    let mail = MailServer::default();
+    let bus = Bus::builder()
+        .add_event_handler(open_account)
+        .add_event_middleware(AssignTenant::default())
+        .append_resources(mail.clone())
+        .manual_event_processing()
+        .build()
+        .unwrap();
+
+    bus.publish(AccountOpened::default()).await.unwrap();
+    bus.run_until_idle().await;
+
+    let letter = &bus.dead_letters()[0];
+    assert_eq!(letter.event::<AccountOpened>().unwrap().tenant.as_deref(), Some("acme"));
+}
+
+#[tokio::test]
+async fn redeliver_should_pass_stored_event_to_handler_without_middlewares() {
+    let mail = MailServer::default();
+    let middleware = AssignTenant::default();
+    let bus = Bus::builder()
+        .add_event_handler(open_account)
+        .add_event_middleware(middleware.clone())
+        .append_resources(mail.clone())
+        .manual_event_processing()
+        .build()
+        .unwrap();
+
+    bus.publish(AccountOpened::default()).await.unwrap();
+    bus.run_until_idle().await;
+    let id = bus.dead_letters()[0].id;
+
+    mail.online.store(true, Ordering::SeqCst);
+    let res = bus.redeliver(id).await;
+
+    assert!(res.is_ok());
+    assert_eq!(middleware.0.load(Ordering::SeqCst), 1);
+    assert_eq!(mail.sent.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn redeliver_should_call_failed_handler_when_handlers_have_the_same_name() {
+    let mail = MailServer::default();
+    let always_failing = |_mail: MailServer, _evt: UserCreated| async move { Err::<(), _>(MailError) };
+    let bus = Bus::builder()
+        .add_event_handler(always_failing)
+        .add_event_handler(|mail: MailServer, evt: UserCreated| send_mail(mail, evt))
+        .append_resources(mail.clone())
+        .manual_event_processing()
+        .build()
+        .unwrap();
+
+    bus.publish(UserCreated { name: "John".into() }).await.unwrap();
+    bus.run_until_idle().await;
+    let letters = bus.dead_letters();
+    assert_eq!(letters.len(), 2);
+
+    mail.online.store(true, Ordering::SeqCst);
+    let res = bus.redeliver(letters[1].id).await;
+
+    assert!(res.is_ok());
+    assert_eq!(mail.sent.load(Ordering::SeqCst), 1);
+    assert_eq!(bus.dead_letters().len(), 1);
+}
+
+#[derive(Clone, MediEvent)]
+struct UserCreated {
+    name: String,
+}
+
+#[derive(Clone, Default, MediRessource)]
+struct MailServer {
+    online: Arc<AtomicBool>,
+    sent: Arc<AtomicUsize>,
+    stats: Arc<AtomicUsize>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Mail server offline")]
+struct MailError;
+
+async fn send_mail(mail: MailServer, _evt: UserCreated) -> core::result::Result<(), MailError> {
+    if !mail.online.load(Ordering::SeqCst) {
+        return Err(MailError);
+    }
+    mail.sent.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+async fn update_stats(mail: MailServer, _evt: UserCreated) -> Result<()> {
+    mail.stats.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+#[derive(Clone, Default, MediEvent)]
+struct AccountOpened {
+    tenant: Option<String>,
+}
+
+async fn open_account(mail: MailServer, evt: AccountOpened) -> core::result::Result<(), MailError> {
+    if !mail.online.load(Ordering::SeqCst) || evt.tenant.is_none() {
+        return Err(MailError);
+    }
+    mail.sent.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Middleware which assigns the tenant to each event and counts its calls
+#[derive(Clone, Default)]
+struct AssignTenant(Arc<AtomicUsize>);
+
+impl EventMiddleware for AssignTenant {
+    fn handle<'a>(&'a self, mut evt: Event, next: EventNext<'a>) -> EventMiddlewareFuture<'a> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        if let Some(evt) = evt.downcast_mut::<AccountOpened>() {
+            evt.tenant = Some("acme".into());
+        }
+        next.run(evt)
+    }
+}
+
+/// Store which only counts the dead letters
+#[derive(Clone, Default)]
+struct CountingStore(Arc<AtomicUsize>);
+
+impl DeadLetterStore for CountingStore {
+    fn push(&self, _letter: DeadLetter) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn list(&self) -> Vec<DeadLetter> {
+        Vec::new()
+    }
+
+    fn get(&self, _id: DeadLetterId) -> Option<DeadLetter> {
+        None
+    }
+
+    fn remove(&self, _id: DeadLetterId) -> Option<DeadLetter> {
+        None
+    }
+}
@@ -0,0 +1,227 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
+use std::time::Duration;
+
+use medi_rs::{Bus, FromResources, HandlerConfig, IntoCommand, IntoEvent, RetryPolicy};
+use medi_rs_macros::{MediCommand, MediEvent, MediRessource};
+
+#[tokio::test]
+async fn send_should_retry_request_handler_until_it_succeeds() {
+    let db = Database::failing(2, DbError::Deadlock);
+    let bus = Bus::builder()
+        .add_req_handler_with(save_user, HandlerConfig::new().retry(fixed(3)))
+        .append_resources(db.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.send(SaveUser).await;
+
+    assert!(res.is_ok());
+    assert_eq!(db.attempts(), 3);
+}
+
+#[tokio::test]
+async fn send_should_return_error_when_max_attempts_reached() {
+    let db = Database::failing(5, DbError::Deadlock);
+    let bus = Bus::builder()
+        .add_req_handler_with(save_user, HandlerConfig::new().retry(fixed(3)))
+        .append_resources(db.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.send(SaveUser).await;
+
+    assert!(matches!(
+        res.unwrap_err().get_handler_error::<DbError>(),
+        Some(DbError::Deadlock)
+    ));
+    assert_eq!(db.attempts(), 3);
+}
+
+#[tokio::test]
+async fn send_should_only_retry_errors_matching_the_predicate() {
+    let db = Database::failing(5, DbError::ConstraintViolation);
+    let policy = fixed(3).retry_on(|e: &DbError| matches!(e, DbError::Deadlock));
+    let bus = Bus::builder()
+        .add_req_handler_with(save_user, HandlerConfig::new().retry(policy))
+        .append_resources(db.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.send(SaveUser).await;
+
+    assert!(res.is_err());
+    assert_eq!(db.attempts(), 1);
+}
+
+#[tokio::test]
+async fn publish_should_retry_event_handler_with_global_policy() {
+    let db = Database::failing(1, DbError::Deadlock);
+    let bus = Bus::builder()
+        .add_event_handler(user_saved)
+        .retry_policy(RetryPolicy::exponential(
+            3,
+            Duration::from_millis(1),
+            Duration::from_millis(10),
+        ))
+        .append_resources(db.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.publish_and_wait(UserSaved).await;
+
+    assert!(res.is_ok());
+    assert_eq!(db.attempts(), 2);
+}
+
+#[tokio::test]
+async fn handler_policy_should_override_global_policy() {
+    let db = Database::failing(5, DbError::Deadlock);
+    let bus = Bus::builder()
+        .add_event_handler_with(user_saved, HandlerConfig::new().retry(fixed(2)))
+        .retry_policy(fixed(4))
+        .append_resources(db.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.publish_and_wait(UserSaved).await;
+
+    assert!(res.is_err());
+    assert_eq!(db.attempts(), 2);
+}
+
+#[tokio::test]
+async fn send_should_retry_request_handler_with_global_policy() {
+    let db = Database::failing(2, DbError::Deadlock);
+    let bus = Bus::builder()
+        .add_req_handler(save_user)
+        .retry_policy(fixed(3))
+        .append_resources(db.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.send(SaveUser).await;
+
+    assert!(res.is_ok());
+    assert_eq!(db.attempts(), 3);
+}
+
+#[tokio::test]
+async fn send_should_not_retry_request_which_is_not_clone() {
+    let db = Database::failing(1, DbError::Deadlock);
+    let bus = Bus::builder()
+        .add_req_handler(delete_user)
+        .retry_policy(fixed(3))
+        .append_resources(db.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.send(DeleteUser).await;
+
+    assert!(res.is_err());
+    assert_eq!(db.attempts(), 1);
+}
+
+#[tokio::test]
+async fn send_should_retry_blocking_request_handler_with_config() {
+    let db = Database::failing(2, DbError::Deadlock);
+    let bus = Bus::builder()
+        .add_blocking_req_handler_with(save_user_blocking, HandlerConfig::new().retry(fixed(3)))
+        .append_resources(db.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.send(SaveUser).await;
+
+    assert!(res.is_ok());
+    assert_eq!(db.attempts(), 3);
+}
+
+#[tokio::test]
+async fn publish_should_retry_blocking_event_handler_with_config() {
+    let db = Database::failing(1, DbError::Deadlock);
+    let bus = Bus::builder()
+        .add_blocking_event_handler_with(user_saved_blocking, HandlerConfig::new().retry(fixed(2)))
+        .append_resources(db.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.publish_and_wait(UserSaved).await;
+
+    assert!(res.is_ok());
+    assert_eq!(db.attempts(), 2);
+}
+
+fn fixed(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::fixed(max_attempts, Duration::from_millis(1))
+}
+
+#[derive(Clone, MediCommand)]
+struct SaveUser;
+
+/// Not `Clone`, therefore not retried
+#[derive(MediCommand)]
+struct DeleteUser;
+
+#[derive(Clone, MediEvent)]
+struct UserSaved;
+
+#[derive(Debug, Clone, thiserror::Error)]
+enum DbError {
+    #[error("Deadlock detected")]
+    Deadlock,
+    #[error("Constraint violation")]
+    ConstraintViolation,
+}
+
+/// Database which fails the given number of times before it succeeds
+#[derive(Clone, MediRessource)]
+struct Database {
+    failures: u32,
+    error: DbError,
+    attempts: Arc<AtomicU32>,
+}
+
+impl Database {
+    fn failing(failures: u32, error: DbError) -> Self {
+        Self {
+            failures,
+            error,
+            attempts: Arc::new(AtomicU32::new(0)),
+        }
+    }
+
+    fn save(&self) -> Result<(), DbError> {
+        let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        if attempt <= self.failures {
+            return Err(self.error.clone());
+        }
+        Ok(())
+    }
+
+    fn attempts(&self) -> u32 {
+        self.attempts.load(Ordering::SeqCst)
+    }
+}
+
+async fn save_user(db: Database, _req: SaveUser) -> Result<(), DbError> {
+    db.save()
+}
+
+async fn user_saved(db: Database, _evt: UserSaved) -> Result<(), DbError> {
+    db.save()
+}
+
+async fn delete_user(db: Database, _req: DeleteUser) -> Result<(), DbError> {
+    db.save()
+}
+
+fn save_user_blocking(db: Database, _req: SaveUser) -> Result<(), DbError> {
+    db.save()
+}
+
+fn user_saved_blocking(db: Database, _evt: UserSaved) -> Result<(), DbError> {
+    db.save()
+}
@@ -0,0 +1,116 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
+use std::time::Duration;
+
+use medi_rs::{Bus, Error, FromResources, HandlerConfig, IntoCommand, IntoEvent, Result, RetryPolicy};
+use medi_rs_macros::{MediCommand, MediEvent, MediRessource};
+
+#[tokio::test]
+async fn send_should_return_timeout_error_and_cancel_handler() {
+    let counter = Counter::default();
+    let bus = Bus::builder()
+        .add_req_handler(slow_request)
+        .handler_timeout(Duration::from_millis(50))
+        .append_resources(counter.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.send(SlowRequest(500)).await;
+
+    let Err(Error::Timeout { handler, elapsed }) = res else {
+        panic!("Expected Timeout, got {:?}", res);
+    };
+    assert!(handler.ends_with("slow_request"));
+    assert!(elapsed >= Duration::from_millis(50));
+    tokio::time::sleep(Duration::from_millis(600)).await; // the handler would have completed by now
+    assert_eq!(counter.get(), 0);
+}
+
+#[tokio::test]
+async fn handler_timeout_should_override_bus_timeout() {
+    let counter = Counter::default();
+    let bus = Bus::builder()
+        .add_req_handler_with(slow_request, HandlerConfig::new().timeout(Duration::from_millis(500)))
+        .handler_timeout(Duration::from_millis(10))
+        .append_resources(counter.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.send(SlowRequest(50)).await;
+
+    assert!(res.is_ok());
+    assert_eq!(counter.get(), 1);
+}
+
+#[tokio::test]
+async fn publish_and_wait_should_report_timed_out_event_handler() {
+    let counter = Counter::default();
+    let bus = Bus::builder()
+        .add_event_handler_with(slow_event, HandlerConfig::new().timeout(Duration::from_millis(50)))
+        .add_event_handler(fast_event)
+        .append_resources(counter.clone())
+        .build()
+        .unwrap();
+
+    let res = bus.publish_and_wait(SlowEvent(500)).await;
+
+    let Err(Error::EventProcessingError(failures)) = res else {
+        panic!("Expected EventProcessingError, got {:?}", res);
+    };
+    assert_eq!(failures.len(), 1);
+    assert!(matches!(failures[0].error, Error::Timeout { .. }));
+    assert_eq!(counter.get(), 1);
+}
+
+#[tokio::test]
+async fn retry_policy_should_retry_timed_out_attempts() {
+    let counter = Counter::default();
+    let policy = RetryPolicy::fixed(3, Duration::ZERO).retry_if(|e| matches!(e, Error::Timeout { .. }));
+    let config = HandlerConfig::new().timeout(Duration::from_millis(20)).retry(policy);
+    let bus = Bus::builder()
+        .add_req_handler_with(slow_request, config)
+        .append_resources(counter.clone())
+        .build()
+        .unwrap();
+
+    let watch = std::time::Instant::now();
+    let res = bus.send(SlowRequest(500)).await;
+
+    assert!(matches!(res, Err(Error::Timeout { .. })));
+    assert!(watch.elapsed() >= Duration::from_millis(60));
+    assert!(watch.elapsed() < Duration::from_millis(500));
+}
+
+#[derive(Clone, MediCommand)]
+struct SlowRequest(u64);
+
+#[derive(Clone, MediEvent)]
+struct SlowEvent(u64);
+
+#[derive(Clone, Default, MediRessource)]
+struct Counter(Arc<AtomicU32>);
+
+impl Counter {
+    fn get(&self) -> u32 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+async fn slow_request(counter: Counter, req: SlowRequest) -> Result<()> {
+    tokio::time::sleep(Duration::from_millis(req.0)).await;
+    counter.0.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+async fn slow_event(counter: Counter, evt: SlowEvent) -> Result<()> {
+    tokio::time::sleep(Duration::from_millis(evt.0)).await;
+    counter.0.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+async fn fast_event(counter: Counter, _evt: SlowEvent) -> Result<()> {
+    counter.0.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use futures::StreamExt;
+use medi_rs::{Bus, FromResources, IntoCommand, IntoStreamCommand, Result};
+use medi_rs_macros::{MediCommand, MediRessource, MediStreamCommand};
+
+#[tokio::test]
+async fn send_stream_should_return_items_of_stream_handler() {
+    let bus = Bus::builder()
+        .add_stream_handler(list_users)
+        .append_resources(UserRepository {
+            users: vec!["John".into(), "Jane".into(), "Jack".into()],
+        })
+        .build()
+        .unwrap();
+
+    let stream = bus.send_stream(ListUsers { limit: 2 }).unwrap();
+    let users: Vec<String> = stream.map(|user| user.unwrap()).collect().await;
+
+    assert_eq!(users, vec!["John".to_string(), "Jane".to_string()]);
+}
+
+#[tokio::test]
+async fn send_stream_should_yield_handler_errors() {
+    let bus = Bus::builder().add_stream_handler(count_down).build().unwrap();
+
+    let items: Vec<Result<u32>> = bus.send_stream(CountDown(2)).unwrap().collect().await;
+
+    assert_eq!(items.len(), 3);
+    assert!(matches!(items[0], Ok(2)));
+    assert!(matches!(items[1], Ok(1)));
+    assert!(
+        items[2]
+            .as_ref()
+            .unwrap_err()
+            .get_handler_error::<CountDownError>()
+            .is_some()
+    );
+}
+
+#[tokio::test]
+async fn build_should_return_error_when_resource_of_stream_handler_is_missing() {
+    let res = Bus::builder().add_stream_handler(list_users).build();
+
+    let Err(medi_rs::Error::InvalidConfiguration(errors)) = res else {
+        panic!("expected an invalid configuration");
+    };
+    assert!(matches!(errors.as_slice(), [medi_rs::Error::MissingResource { .. }]));
+}
+
+#[tokio::test]
+async fn send_stream_should_return_error_when_no_stream_handler_is_registered() {
+    let bus = Bus::builder().add_req_handler(get_user).build().unwrap();
+
+    let res = bus.send_stream(ListUsers { limit: 2 });
+
+    match res {
+        Err(medi_rs::Error::HandlerNotFound { request }) => assert!(request.ends_with("ListUsers")),
+        _ => panic!("expected HandlerNotFound"),
+    }
+}
+
+#[derive(MediStreamCommand)]
+#[medi_stream_command(item_type = String)]
+struct ListUsers {
+    limit: usize,
+}
+
+#[derive(MediStreamCommand)]
+#[medi_stream_command(item_type = u32)]
+struct CountDown(u32);
+
+#[derive(MediCommand)]
+#[medi_command(return_type = String)]
+struct GetUser;
+
+#[derive(Clone, MediRessource)]
+struct UserRepository {
+    users: Vec<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Count down finished")]
+struct CountDownError;
+
+fn list_users(repo: UserRepository, req: ListUsers) -> impl futures::Stream<Item = Result<String>> {
+    let users: Vec<Result<String>> = repo.users.into_iter().take(req.limit).map(Ok).collect();
+    futures::stream::iter(users)
+}
+
+fn count_down(req: CountDown) -> impl futures::Stream<Item = core::result::Result<u32, CountDownError>> {
+    futures::stream::unfold(Some(req.0), |state| async move {
+        match state? {
+            0 => Some((Err(CountDownError), None)),
+            n => Some((Ok(n), Some(n - 1))),
+        }
+    })
+}
+
+async fn get_user(_req: GetUser) -> Result<String> {
+    Ok("John".into())
+}
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use medi_rs::{Bus, IntoEvent, LagPolicy, Result, SubscriptionConfig};
+use medi_rs_macros::MediEvent;
+
+#[tokio::test]
+async fn subscribe_should_stream_published_events() {
+    let bus = Bus::builder().add_event_handler(user_created).build().unwrap();
+    let mut users = bus.subscribe::<UserCreated>();
+
+    bus.publish(UserCreated { id: 1 }).await.unwrap();
+    bus.publish(UserCreated { id: 2 }).await.unwrap();
+
+    assert_eq!(users.next().await.unwrap().id, 1);
+    assert_eq!(users.next().await.unwrap().id, 2);
+}
+
+#[tokio::test]
+async fn subscribe_should_receive_events_without_handler() {
+    let bus = Bus::builder().build().unwrap();
+    let mut users = bus.subscribe::<UserCreated>();
+
+    bus.publish(UserCreated { id: 1 }).await.unwrap();
+
+    let user = tokio::time::timeout(Duration::from_secs(1), users.next())
+        .await
+        .unwrap();
+    assert_eq!(user.unwrap().id, 1);
+}
+
+#[tokio::test]
+async fn subscribe_should_drop_oldest_events_when_buffer_is_full() {
+    let bus = Bus::builder().build().unwrap();
+    let mut users = bus.subscribe_with::<UserCreated>(SubscriptionConfig::new().capacity(2));
+
+    for id in 1..=4 {
+        bus.publish(UserCreated { id }).await.unwrap();
+    }
+    bus.drain(None).await;
+
+    let ids: Vec<u32> = users.by_ref().map(|user| user.id).collect().await;
+    assert_eq!(ids, vec![3, 4]);
+    assert_eq!(users.lagged(), 2);
+}
+
+#[tokio::test]
+async fn subscribe_should_drop_newest_events_when_buffer_is_full() {
+    let bus = Bus::builder().build().unwrap();
+    let config = SubscriptionConfig::new().capacity(2).lag_policy(LagPolicy::DropNewest);
+    let users = bus.subscribe_with::<UserCreated>(config);
+
+    for id in 1..=4 {
+        bus.publish(UserCreated { id }).await.unwrap();
+    }
+    bus.drain(None).await;
+
+    let ids: Vec<u32> = users.map(|user| user.id).collect().await;
+    assert_eq!(ids, vec![1, 2]);
+}
+
+#[tokio::test]
+async fn dropped_subscription_should_not_receive_events() {
+    let bus = Bus::builder().build().unwrap();
+    let first = bus.subscribe::<UserCreated>();
+    let second = bus.subscribe::<UserCreated>();
+    drop(first);
+
+    bus.publish(UserCreated { id: 1 }).await.unwrap();
+    bus.drain(None).await;
+
+    let ids: Vec<u32> = second.map(|user| user.id).collect().await;
+    assert_eq!(ids, vec![1]);
+}
+
+#[tokio::test]
+async fn subscription_should_end_after_shutdown() {
+    let bus = Bus::builder().build().unwrap();
+    bus.shutdown(None).await;
+
+    let mut users = bus.subscribe::<UserCreated>();
+
+    assert!(users.next().await.is_none());
+}
+
+#[derive(Clone, MediEvent)]
+struct UserCreated {
+    id: u32,
+}
+
+async fn user_created(_evt: UserCreated) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use medi_rs::{Bus, IntoEvent};
+use medi_rs_macros::MediEvent;
+
+#[tokio::test]
+async fn wait_for_should_return_first_matching_event() {
+    let bus = Bus::builder().build().unwrap();
+    let order = bus.wait_for(|evt: &OrderShipped| evt.id == 2, Duration::from_secs(1));
+
+    bus.publish(OrderShipped { id: 1 }).await.unwrap();
+    bus.publish(OrderShipped { id: 2 }).await.unwrap();
+
+    assert_eq!(order.await.unwrap().id, 2);
+}
+
+#[tokio::test]
+async fn wait_for_should_see_events_published_by_other_tasks() {
+    let bus = Bus::builder().build().unwrap();
+    let publisher = bus.clone();
+
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        publisher.publish(OrderShipped { id: 7 }).await.unwrap();
+    });
+    let order = bus.wait_for(|_: &OrderShipped| true, Duration::from_secs(1)).await;
+
+    assert_eq!(order.unwrap().id, 7);
+}
+
+#[tokio::test]
+async fn wait_for_should_return_error_on_timeout() {
+    let bus = Bus::builder().build().unwrap();
+    let order = bus.wait_for(|evt: &OrderShipped| evt.id == 2, Duration::from_millis(50));
+
+    bus.publish(OrderShipped { id: 1 }).await.unwrap();
+
+    let err = order.await.unwrap_err();
+    assert!(matches!(err, medi_rs::Error::EventWaitTimeout { elapsed, .. } if elapsed == Duration::from_millis(50)));
+}
+
+#[tokio::test]
+async fn wait_for_should_return_error_when_bus_is_shut_down() {
+    let bus = Bus::builder().build().unwrap();
+    let order = bus.wait_for(|_: &OrderShipped| true, Duration::from_secs(1));
+
+    bus.shutdown(None).await;
+
+    assert!(matches!(order.await, Err(medi_rs::Error::EventPublishingError)));
+}
+
+#[derive(Debug, Clone, MediEvent)]
+struct OrderShipped {
+    id: u32,
+}
@@ -0,0 +1,155 @@
+use std::sync::{Arc, Mutex};
+
+use medi_rs::{Bus, FromResources, IntoEvent, Result};
+use medi_rs_macros::{MediEvent, MediRessource};
+
+#[tokio::test]
+async fn manual_event_processing_should_queue_events_until_run_until_idle() {
+    let log = EventLog::default();
+    let bus = Bus::builder()
+        .add_event_handler(order_placed)
+        .append_resources(log.clone())
+        .manual_event_processing()
+        .build()
+        .unwrap();
+
+    bus.publish(OrderPlaced { id: 1 }).await.unwrap();
+    bus.publish(OrderPlaced { id: 2 }).await.unwrap();
+    tokio::task::yield_now().await;
+    assert!(log.entries().is_empty());
+
+    let processed = bus.run_until_idle().await;
+
+    assert_eq!(processed, 2);
+    assert_eq!(log.entries(), vec!["placed 1", "placed 2"]);
+}
+
+#[tokio::test]
+async fn manual_event_processing_should_not_limit_queued_events() {
+    let log = EventLog::default();
+    let bus = Bus::builder()
+        .add_event_handler(order_placed)
+        .append_resources(log.clone())
+        .manual_event_processing()
+        .build()
+        .unwrap();
+
+    let publish_all = async {
+        for id in 0..2000 {
+            bus.publish(OrderPlaced { id }).await.unwrap();
+        }
+    };
+    let published = tokio::time::timeout(tokio::time::Duration::from_secs(1), publish_all).await;
+    assert!(published.is_ok());
+
+    let processed = bus.run_until_idle().await;
+
+    assert_eq!(processed, 2000);
+    assert_eq!(log.entries().len(), 2000);
+}
+
+#[tokio::test]
+async fn run_until_idle_should_process_events_published_by_handlers() {
+    let log = EventLog::default();
+    let bus = Bus::builder()
+        .add_event_handler(place_and_ship)
+        .add_event_handler(order_shipped)
+        .append_resources(log.clone())
+        .manual_event_processing()
+        .build()
+        .unwrap();
+
+    bus.publish(OrderPlaced { id: 1 }).await.unwrap();
+    let processed = bus.run_until_idle().await;
+
+    assert_eq!(processed, 2);
+    assert_eq!(log.entries(), vec!["shipped 1"]);
+    assert_eq!(bus.run_until_idle().await, 0);
+}
+
+#[tokio::test]
+async fn run_until_idle_should_keep_dead_letters_of_failed_handlers() {
+    let bus = Bus::builder()
+        .add_event_handler(failing_handler)
+        .manual_event_processing()
+        .build()
+        .unwrap();
+
+    bus.publish(OrderPlaced { id: 1 }).await.unwrap();
+    bus.run_until_idle().await;
+
+    assert_eq!(bus.dead_letters().len(), 1);
+}
+
+#[tokio::test]
+async fn drain_should_process_queued_events_in_manual_mode() {
+    let log = EventLog::default();
+    let bus = Bus::builder()
+        .add_event_handler(order_placed)
+        .append_resources(log.clone())
+        .manual_event_processing()
+        .build()
+        .unwrap();
+
+    bus.publish(OrderPlaced { id: 1 }).await.unwrap();
+    let report = bus.drain(None).await;
+
+    assert!(report.unprocessed_events.is_empty());
+    assert_eq!(log.entries(), vec!["placed 1"]);
+}
+
+#[tokio::test]
+async fn shutdown_should_report_queued_events_in_manual_mode() {
+    let bus = Bus::builder()
+        .add_event_handler(order_placed)
+        .append_resources(EventLog::default())
+        .manual_event_processing()
+        .build()
+        .unwrap();
+
+    bus.publish(OrderPlaced { id: 1 }).await.unwrap();
+    let report = bus.shutdown(None).await;
+
+    assert_eq!(report.unprocessed_events.len(), 1);
+}
+
+#[derive(Clone, MediEvent)]
+struct OrderPlaced {
+    id: u32,
+}
+
+#[derive(Clone, MediEvent)]
+struct OrderShipped {
+    id: u32,
+}
+
+#[derive(Clone, Default, MediRessource)]
+struct EventLog(Arc<Mutex<Vec<String>>>);
+
+impl EventLog {
+    fn entries(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Out of stock")]
+struct OutOfStock;
+
+async fn order_placed(log: EventLog, evt: OrderPlaced) -> Result<()> {
+    log.0.lock().unwrap().push(format!("placed {}", evt.id));
+    Ok(())
+}
+
+async fn place_and_ship(bus: Bus, evt: OrderPlaced) -> Result<()> {
+    bus.publish(OrderShipped { id: evt.id }).await
+}
+
+async fn order_shipped(log: EventLog, evt: OrderShipped) -> Result<()> {
+    log.0.lock().unwrap().push(format!("shipped {}", evt.id));
+    Ok(())
+}
+
+async fn failing_handler(_evt: OrderPlaced) -> core::result::Result<(), OutOfStock> {
+    Err(OutOfStock)
+}
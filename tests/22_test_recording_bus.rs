@@ -0,0 +1,126 @@
+#![cfg(feature = "testing")]
+
+use medi_rs::testing::RecordingBus;
+use medi_rs::{Bus, IntoCommand, IntoEvent, Result};
+use medi_rs_macros::{MediCommand, MediEvent};
+
+#[tokio::test]
+async fn recording_bus_should_record_published_events_of_handler() {
+    let recorder = RecordingBus::new();
+    recorder.stub(|req: &ReserveStock| Ok(req.quantity <= 10));
+
+    let res = place_order(recorder.bus(), PlaceOrder { id: 1, quantity: 2 }).await;
+
+    assert!(res.is_ok());
+    recorder.assert_sent(|req: &ReserveStock| req.quantity == 2);
+    recorder.assert_published(|evt: &OrderPlaced| evt.id == 1);
+    recorder.assert_not_published::<OrderRejected>();
+    assert_eq!(recorder.published::<OrderPlaced>(), vec![OrderPlaced { id: 1 }]);
+}
+
+#[tokio::test]
+async fn recording_bus_should_use_stubbed_response() {
+    let recorder = RecordingBus::new();
+    recorder.stub(|_: &ReserveStock| Ok(false));
+
+    place_order(recorder.bus(), PlaceOrder { id: 1, quantity: 20 })
+        .await
+        .unwrap();
+
+    recorder.assert_published(|evt: &OrderRejected| evt.id == 1);
+    assert_eq!(recorder.published_count::<OrderPlaced>(), 0);
+    assert_eq!(recorder.sent_count::<ReserveStock>(), 1);
+}
+
+#[tokio::test]
+async fn recording_bus_should_return_error_for_request_without_stub() {
+    let recorder = RecordingBus::new();
+
+    let res = place_order(recorder.bus(), PlaceOrder { id: 1, quantity: 2 }).await;
+
+    assert!(res.unwrap_err().is_handler_not_found());
+    assert_eq!(recorder.sent::<ReserveStock>().len(), 1);
+}
+
+#[tokio::test]
+async fn recording_bus_should_record_events_processed_by_code_under_test() {
+    let recorder = RecordingBus::new();
+    recorder.stub(|_: &ReserveStock| Ok(true));
+    let bus = recorder.bus();
+
+    place_order(bus.clone(), PlaceOrder { id: 1, quantity: 2 })
+        .await
+        .unwrap();
+    bus.run_until_idle().await;
+    place_order(bus, PlaceOrder { id: 2, quantity: 2 }).await.unwrap();
+
+    assert_eq!(
+        recorder.published::<OrderPlaced>(),
+        vec![OrderPlaced { id: 1 }, OrderPlaced { id: 2 }]
+    );
+}
+
+#[tokio::test]
+async fn recording_bus_should_record_more_events_than_queue_capacity() {
+    let recorder = RecordingBus::new();
+
+    for id in 0..2000 {
+        recorder.bus().publish(OrderPlaced { id }).await.unwrap();
+    }
+
+    assert_eq!(recorder.published_count::<OrderPlaced>(), 2000);
+}
+
+#[tokio::test]
+async fn recording_bus_should_record_events_published_and_waited_for() {
+    let recorder = RecordingBus::new();
+    recorder.bus().publish(OrderPlaced { id: 1 }).await.unwrap();
+
+    let res = recorder.bus().publish_and_wait(OrderPlaced { id: 2 }).await;
+
+    assert!(res.is_ok());
+    assert_eq!(
+        recorder.published::<OrderPlaced>(),
+        vec![OrderPlaced { id: 1 }, OrderPlaced { id: 2 }]
+    );
+}
+
+#[tokio::test]
+#[should_panic(expected = "No matching event")]
+async fn assert_published_should_panic_when_no_event_matches() {
+    let recorder = RecordingBus::new();
+    recorder.bus().publish(OrderPlaced { id: 1 }).await.unwrap();
+
+    recorder.assert_published(|evt: &OrderPlaced| evt.id == 2);
+}
+
+#[derive(MediCommand)]
+struct PlaceOrder {
+    id: u32,
+    quantity: u32,
+}
+
+#[derive(Clone, MediCommand)]
+#[medi_command(return_type = bool)]
+struct ReserveStock {
+    quantity: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, MediEvent)]
+struct OrderPlaced {
+    id: u32,
+}
+
+#[derive(Clone, MediEvent)]
+struct OrderRejected {
+    id: u32,
+}
+
+async fn place_order(bus: Bus, req: PlaceOrder) -> Result<()> {
+    let reserved = bus.send(ReserveStock { quantity: req.quantity }).await?;
+    if reserved {
+        bus.publish(OrderPlaced { id: req.id }).await
+    } else {
+        bus.publish(OrderRejected { id: req.id }).await
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::{Arc, Mutex};
+
+use medi_rs::{FromResources, IntoCommand, IntoEvent, Resources, Result, invoke_handler};
+use medi_rs_macros::{MediCommand, MediEvent, MediRessource};
+
+#[tokio::test]
+async fn invoke_handler_should_call_request_handler_with_resources() {
+    let resources = Resources::builder()
+        .with(UserRepository::with_users(&["John"]))
+        .with(Greeting("Hello".into()))
+        .build();
+
+    let res = invoke_handler(greet_user, &resources, GreetUser { id: 0 }).await;
+
+    assert_eq!(res.unwrap(), "Hello John");
+}
+
+#[tokio::test]
+async fn invoke_handler_should_call_event_handler_with_resources() {
+    let repo = UserRepository::default();
+    let resources = Resources::builder().with(repo.clone()).build();
+
+    invoke_handler(user_created, &resources, UserCreated { name: "Jane".into() })
+        .await
+        .unwrap();
+
+    assert_eq!(repo.0.lock().unwrap().as_slice(), ["Jane".to_string()]);
+}
+
+#[tokio::test]
+async fn invoke_handler_should_return_error_when_resource_is_missing() {
+    let resources = Resources::builder().with(Greeting("Hello".into())).build();
+
+    let res = invoke_handler(greet_user, &resources, GreetUser { id: 0 }).await;
+
+    let Err(medi_rs::Error::ResourceNotFound { resource, handler }) = res else {
+        panic!("expected ResourceNotFound");
+    };
+    assert!(resource.ends_with("UserRepository"));
+    assert!(handler.unwrap().ends_with("greet_user"));
+}
+
+#[test]
+fn resources_builder_should_replace_resource_of_same_type() {
+    let resources = Resources::builder()
+        .with(Greeting("Hello".into()))
+        .with(Greeting("Hi".into()))
+        .build();
+
+    assert_eq!(resources.get::<Greeting>().unwrap().0, "Hi");
+}
+
+#[derive(MediCommand)]
+#[medi_command(return_type = String)]
+struct GreetUser {
+    id: usize,
+}
+
+#[derive(Clone, MediEvent)]
+struct UserCreated {
+    name: String,
+}
+
+#[derive(Clone, Default, MediRessource)]
+struct UserRepository(Arc<Mutex<Vec<String>>>);
+
+impl UserRepository {
+    fn with_users(users: &[&str]) -> Self {
+        UserRepository(Arc::new(Mutex::new(
+            users.iter().map(|user| user.to_string()).collect(),
+        )))
+    }
+}
+
+#[derive(Clone, MediRessource)]
+struct Greeting(String);
+
+async fn greet_user(repo: UserRepository, greeting: Greeting, req: GreetUser) -> Result<String> {
+    let name = repo.0.lock().unwrap()[req.id].clone();
+    Ok(format!("{} {}", greeting.0, name))
+}
+
+async fn user_created(repo: UserRepository, evt: UserCreated) -> Result<()> {
+    repo.0.lock().unwrap().push(evt.name);
+    Ok(())
+}
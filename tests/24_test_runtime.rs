@@ -0,0 +1,112 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use medi_rs::{Bus, FromResources, HandlerConfig, IntoCommand, IntoEvent, Result, RetryPolicy, Spawner};
+use medi_rs_macros::{MediCommand, MediEvent, MediRessource};
+
+#[tokio::test]
+async fn bus_should_run_handlers_with_custom_spawner() {
+    let spawner = CountingSpawner::default();
+    let counter = Counter::default();
+    let bus = Bus::builder()
+        .add_req_handler_with(flaky, HandlerConfig::new().retry(RetryPolicy::fixed(3, Duration::ZERO)))
+        .add_event_handler(count_event)
+        .append_resources(counter.clone())
+        .spawner(spawner.clone())
+        .build()
+        .unwrap();
+
+    assert_eq!(bus.send(Flaky).await.unwrap(), 3);
+    bus.publish_and_wait(Ping).await.unwrap();
+
+    assert_eq!(counter.0.load(Ordering::SeqCst), 4);
+    // event loop and the event handler
+    assert_eq!(spawner.0.load(Ordering::SeqCst), 2);
+}
+
+#[cfg(feature = "smol")]
+#[test]
+fn bus_should_run_on_smol() {
+    smol::block_on(async {
+        let counter = Counter::default();
+        let bus = Bus::builder()
+            .spawner(medi_rs::SmolSpawner)
+            .add_req_handler_with(flaky, HandlerConfig::new().retry(RetryPolicy::fixed(3, Duration::ZERO)))
+            .add_event_handler(count_event)
+            .append_resources(counter.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(bus.send(Flaky).await.unwrap(), 3);
+        bus.publish(Ping).await.unwrap();
+        let report = bus.drain(Some(Duration::from_secs(1))).await;
+
+        assert!(!report.deadline_exceeded);
+        assert_eq!(counter.0.load(Ordering::SeqCst), 4);
+    });
+}
+
+#[cfg(feature = "async-std")]
+#[test]
+fn bus_should_run_on_async_std() {
+    async_std::task::block_on(async {
+        let counter = Counter::default();
+        let bus = Bus::builder()
+            .spawner(medi_rs::AsyncStdSpawner)
+            .add_req_handler_with(flaky, HandlerConfig::new().retry(RetryPolicy::fixed(3, Duration::ZERO)))
+            .add_event_handler(count_event)
+            .append_resources(counter.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(bus.send(Flaky).await.unwrap(), 3);
+        bus.publish(Ping).await.unwrap();
+        bus.drain(Some(Duration::from_secs(1))).await;
+
+        assert_eq!(counter.0.load(Ordering::SeqCst), 4);
+    });
+}
+
+#[derive(Clone, MediEvent)]
+struct Ping;
+
+#[derive(Clone, MediCommand)]
+#[medi_command(return_type = usize)]
+struct Flaky;
+
+#[derive(Clone, Default, MediRessource)]
+struct Counter(Arc<AtomicUsize>);
+
+#[derive(Debug, thiserror::Error)]
+#[error("Not yet")]
+struct NotYet;
+
+async fn count_event(counter: Counter, _evt: Ping) -> Result<()> {
+    counter.0.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+async fn flaky(counter: Counter, _req: Flaky) -> core::result::Result<usize, NotYet> {
+    let attempt = counter.0.fetch_add(1, Ordering::SeqCst) + 1;
+    if attempt < 3 {
+        return Err(NotYet);
+    }
+    Ok(attempt)
+}
+
+/// Spawner which counts the spawned tasks and runs them on tokio
+#[derive(Clone, Default)]
+struct CountingSpawner(Arc<AtomicUsize>);
+
+impl Spawner for CountingSpawner {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        tokio::spawn(future);
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use medi_rs::{Bus, FromResources, IntoEvent, Result};
+use medi_rs_macros::{MediEvent, MediRessource};
+
+#[test]
+fn build_with_event_loop_should_not_need_a_running_runtime() {
+    let counter = Counter::default();
+    let (bus, event_loop) = Bus::builder()
+        .add_event_handler(count_ticks)
+        .append_resources(counter.clone())
+        .build_with_event_loop()
+        .unwrap();
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let event_loop = tokio::spawn(event_loop);
+        bus.publish(Tick).await.unwrap();
+        bus.publish(Tick).await.unwrap();
+
+        let report = bus.drain(Some(Duration::from_secs(1))).await;
+
+        assert!(!report.deadline_exceeded);
+        assert!(event_loop.await.is_ok());
+    });
+    assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn events_should_be_queued_until_event_loop_is_driven() {
+    let counter = Counter::default();
+    let (bus, event_loop) = Bus::builder()
+        .add_event_handler(count_ticks)
+        .append_resources(counter.clone())
+        .build_with_event_loop()
+        .unwrap();
+
+    bus.publish(Tick).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+
+    let tick = bus.wait_for(|_: &Tick| true, Duration::from_secs(1));
+    tokio::spawn(event_loop);
+    bus.publish(Tick).await.unwrap();
+    tick.await.unwrap();
+    bus.drain(None).await;
+
+    assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn shutdown_should_not_wait_for_dropped_event_loop() {
+    let (bus, event_loop) = Bus::builder().build_with_event_loop().unwrap();
+    drop(event_loop);
+
+    bus.publish(Tick).await.unwrap();
+    let report = bus.shutdown(None).await;
+
+    assert_eq!(report.unprocessed_events.len(), 1);
+}
+
+#[derive(Clone, MediEvent)]
+struct Tick;
+
+#[derive(Clone, Default, MediRessource)]
+struct Counter(Arc<AtomicUsize>);
+
+async fn count_ticks(counter: Counter, _evt: Tick) -> Result<()> {
+    counter.0.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
@@ -0,0 +1,189 @@
+#![cfg(feature = "tokio")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use medi_rs::{FromResources, IntoCommand, IntoEvent, LocalBus, Result};
+use medi_rs_macros::{MediCommand, MediEvent, MediRessource};
+use tokio::task::LocalSet;
+
+#[tokio::test]
+async fn local_bus_should_send_request_to_handler_with_rc_resource() {
+    LocalSet::new()
+        .run_until(async {
+            let history = History::default();
+            let bus = LocalBus::builder()
+                .add_req_handler(add_entry)
+                .append_resources(history.clone())
+                .build()
+                .unwrap();
+
+            let count = bus.send(AddEntry("first".into())).await.unwrap();
+
+            assert_eq!(count, 1);
+            assert_eq!(history.0.borrow().as_slice(), ["first".to_string()]);
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn local_bus_should_process_published_events_on_local_set() {
+    LocalSet::new()
+        .run_until(async {
+            let history = History::default();
+            let bus = LocalBus::builder()
+                .add_req_handler(add_entry)
+                .add_event_handler(entry_added)
+                .append_resources(history.clone())
+                .build()
+                .unwrap();
+
+            bus.publish(EntryAdded("published".into())).await.unwrap();
+            tokio::task::yield_now().await;
+            bus.publish_and_wait(EntryAdded("waited".into())).await.unwrap();
+
+            assert_eq!(
+                history.0.borrow().as_slice(),
+                ["published".to_string(), "waited".to_string()]
+            );
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn local_bus_should_return_failed_handlers() {
+    LocalSet::new()
+        .run_until(async {
+            let bus = LocalBus::builder().add_event_handler(failing_handler).build().unwrap();
+
+            let res = bus.publish_and_wait(EntryAdded("failed".into())).await;
+
+            let Err(medi_rs::Error::EventProcessingError(failures)) = res else {
+                panic!("expected failed handlers");
+            };
+            assert_eq!(failures.len(), 1);
+            assert!(failures[0].handler.ends_with("failing_handler"));
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn local_bus_drain_should_process_queued_events_and_stop_accepting_events() {
+    LocalSet::new()
+        .run_until(async {
+            let history = History::default();
+            let bus = LocalBus::builder()
+                .add_req_handler(add_entry)
+                .add_event_handler(entry_added)
+                .append_resources(history.clone())
+                .build()
+                .unwrap();
+
+            bus.publish(EntryAdded("first".into())).await.unwrap();
+            bus.publish(EntryAdded("second".into())).await.unwrap();
+            let report = bus.drain(None).await;
+
+            assert!(report.unprocessed_events.is_empty());
+            assert_eq!(history.0.borrow().len(), 2);
+            let res = bus.publish(EntryAdded("third".into())).await;
+            assert!(matches!(res, Err(medi_rs::Error::EventPublishingError)));
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn local_bus_shutdown_should_report_queued_events() {
+    LocalSet::new()
+        .run_until(async {
+            let history = History::default();
+            let bus = LocalBus::builder()
+                .add_req_handler(add_entry)
+                .add_event_handler(entry_added)
+                .append_resources(history.clone())
+                .build()
+                .unwrap();
+
+            bus.publish(EntryAdded("first".into())).await.unwrap();
+            bus.publish(EntryAdded("second".into())).await.unwrap();
+            let report = bus.shutdown(None).await;
+
+            assert_eq!(report.unprocessed_events.len(), 2);
+            assert!(history.0.borrow().is_empty());
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn local_bus_shutdown_should_cancel_handlers_when_deadline_passes() {
+    LocalSet::new()
+        .run_until(async {
+            let bus = LocalBus::builder().add_event_handler(slow_handler).build().unwrap();
+
+            bus.publish(EntryAdded("slow".into())).await.unwrap();
+            tokio::task::yield_now().await;
+            let report = bus.shutdown(Some(Duration::from_millis(50))).await;
+
+            assert!(report.deadline_exceeded);
+            assert_eq!(report.unprocessed_events.len(), 1);
+            assert!(report.unprocessed_events[0].ends_with("EntryAdded"));
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn local_bus_build_should_return_error_when_request_handler_is_added_twice() {
+    let res = LocalBus::builder()
+        .add_req_handler(add_entry)
+        .add_req_handler(add_entry_twice)
+        .build();
+
+    let Err(medi_rs::Error::InvalidConfiguration(errors)) = res else {
+        panic!("expected an invalid configuration");
+    };
+    let [medi_rs::Error::DuplicateHandler { handlers, .. }] = errors.as_slice() else {
+        panic!("expected a duplicate handler error, got {:?}", errors);
+    };
+    assert!(handlers[0].ends_with("add_entry"));
+    assert!(handlers[1].ends_with("add_entry_twice"));
+}
+
+#[derive(MediCommand)]
+#[medi_command(return_type = usize)]
+struct AddEntry(String);
+
+#[derive(Clone, MediEvent)]
+struct EntryAdded(String);
+
+/// Not `Send`, only usable with the local bus
+#[derive(Clone, Default, MediRessource)]
+struct History(Rc<RefCell<Vec<String>>>);
+
+#[derive(Debug, thiserror::Error)]
+#[error("History is full")]
+struct HistoryFull;
+
+async fn add_entry(history: History, req: AddEntry) -> Result<usize> {
+    history.0.borrow_mut().push(req.0);
+    Ok(history.0.borrow().len())
+}
+
+async fn add_entry_twice(history: History, req: AddEntry) -> Result<usize> {
+    history.0.borrow_mut().push(req.0.clone());
+    history.0.borrow_mut().push(req.0);
+    Ok(history.0.borrow().len())
+}
+
+async fn entry_added(bus: LocalBus, evt: EntryAdded) -> Result<()> {
+    bus.send(AddEntry(evt.0)).await?;
+    Ok(())
+}
+
+async fn failing_handler(_evt: EntryAdded) -> core::result::Result<(), HistoryFull> {
+    Err(HistoryFull)
+}
+
+async fn slow_handler(_evt: EntryAdded) -> Result<()> {
+    tokio::time::sleep(Duration::from_secs(10)).await;
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use medi_rs::{Bus, FromResources, IntoCommand, IntoEvent, Result};
+use medi_rs_macros::{MediCommand, MediEvent, MediRessource};
+
+#[tokio::test]
+async fn blocking_req_handler_should_return_result_with_resources() {
+    let bus = Bus::builder()
+        .add_blocking_req_handler(hash_password)
+        .append_resources(Salt("pepper".into()))
+        .build()
+        .unwrap();
+
+    let res = bus.send(HashPassword("secret".into())).await.unwrap();
+
+    assert_eq!(res, "secret:pepper");
+}
+
+#[tokio::test]
+async fn blocking_req_handler_should_run_outside_of_async_worker() {
+    let bus = Bus::builder().add_blocking_req_handler(current_thread).build().unwrap();
+
+    let handler_thread = bus.send(CurrentThread).await.unwrap();
+
+    assert_ne!(handler_thread, std::thread::current().id());
+}
+
+#[tokio::test]
+async fn build_should_return_error_when_resource_of_blocking_handler_is_missing() {
+    let res = Bus::builder().add_blocking_req_handler(hash_password).build();
+
+    let Err(medi_rs::Error::InvalidConfiguration(errors)) = res else {
+        panic!("expected an invalid configuration");
+    };
+    assert!(matches!(errors.as_slice(), [medi_rs::Error::MissingResource { .. }]));
+}
+
+#[tokio::test]
+async fn blocking_req_handler_should_return_error_when_handler_panics() {
+    let bus = Bus::builder().add_blocking_req_handler(panicking).build().unwrap();
+
+    let res = bus.send(Panicking).await;
+
+    assert!(matches!(res, Err(medi_rs::Error::HandlerPanicked)));
+}
+
+#[tokio::test]
+async fn blocking_event_handler_should_handle_published_event() {
+    let log = AuditLog::default();
+    let bus = Bus::builder()
+        .add_blocking_event_handler(audit_user_created)
+        .append_resources(log.clone())
+        .build()
+        .unwrap();
+
+    bus.publish(UserCreated { name: "John".into() }).await.unwrap();
+    bus.drain(Some(Duration::from_secs(1))).await;
+
+    assert_eq!(log.0.lock().unwrap().as_slice(), ["John".to_string()]);
+}
+
+#[derive(MediCommand)]
+#[medi_command(return_type = String)]
+struct HashPassword(String);
+
+#[derive(MediCommand)]
+#[medi_command(return_type = std::thread::ThreadId)]
+struct CurrentThread;
+
+#[derive(MediCommand)]
+#[medi_command(return_type = ())]
+struct Panicking;
+
+#[derive(Clone, MediEvent)]
+struct UserCreated {
+    name: String,
+}
+
+#[derive(Clone, MediRessource)]
+struct Salt(String);
+
+#[derive(Clone, Default, MediRessource)]
+struct AuditLog(Arc<Mutex<Vec<String>>>);
+
+fn hash_password(salt: Salt, req: HashPassword) -> Result<String> {
+    std::thread::sleep(Duration::from_millis(10));
+    Ok(format!("{}:{}", req.0, salt.0))
+}
+
+fn current_thread(_req: CurrentThread) -> Result<std::thread::ThreadId> {
+    Ok(std::thread::current().id())
+}
+
+fn panicking(_req: Panicking) -> Result<()> {
+    panic!("blocking handler panicked")
+}
+
+fn audit_user_created(log: AuditLog, evt: UserCreated) -> Result<()> {
+    log.0.lock().unwrap().push(evt.name);
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use medi_rs::{Bus, FromResources, IntoCommand, IntoEvent, Result};
+use medi_rs_macros::{MediCommand, MediEvent, MediRessource};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn send_blocking_should_return_response_from_sync_thread() {
+    let bus = Bus::builder()
+        .add_req_handler(get_user)
+        .append_resources(UserRepository::with_users(&["John"]))
+        .build()
+        .unwrap();
+    let handle = bus.handle();
+
+    let res = std::thread::spawn(move || handle.send_blocking(GetUser { id: 0 }))
+        .join()
+        .unwrap();
+
+    assert_eq!(res.unwrap(), "John");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn send_blocking_should_return_handler_error() {
+    let bus = Bus::builder()
+        .add_req_handler(get_user)
+        .append_resources(UserRepository::default())
+        .build()
+        .unwrap();
+    let handle = bus.handle();
+
+    let res = std::thread::spawn(move || handle.send_blocking(GetUser { id: 1 }))
+        .join()
+        .unwrap();
+
+    assert!(res.unwrap_err().get_handler_error::<UserNotFound>().is_some());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn send_blocking_should_run_handler_on_runtime_of_bus() {
+    let bus = Bus::builder().add_req_handler(sleep_in_runtime).build().unwrap();
+    let handle = bus.handle();
+
+    let res = std::thread::spawn(move || handle.send_blocking(SleepInRuntime))
+        .join()
+        .unwrap();
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publish_blocking_should_queue_event_from_sync_thread() {
+    let repo = UserRepository::default();
+    let bus = Bus::builder()
+        .add_event_handler(user_created)
+        .append_resources(repo.clone())
+        .build()
+        .unwrap();
+    let handle = bus.handle();
+
+    std::thread::spawn(move || handle.publish_blocking(UserCreated { name: "Jane".into() }))
+        .join()
+        .unwrap()
+        .unwrap();
+    bus.drain(Some(Duration::from_secs(1))).await;
+
+    assert_eq!(repo.0.lock().unwrap().as_slice(), ["Jane".to_string()]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publish_blocking_should_fail_after_shutdown() {
+    let bus = Bus::builder().build().unwrap();
+    bus.shutdown(None).await;
+    let handle = bus.handle();
+
+    let res = std::thread::spawn(move || handle.publish_blocking(UserCreated { name: "Jane".into() }))
+        .join()
+        .unwrap();
+
+    assert!(matches!(res, Err(medi_rs::Error::EventPublishingError)));
+}
+
+#[derive(MediCommand)]
+#[medi_command(return_type = String)]
+struct GetUser {
+    id: usize,
+}
+
+#[derive(MediCommand)]
+#[medi_command(return_type = ())]
+struct SleepInRuntime;
+
+#[derive(Clone, MediEvent)]
+struct UserCreated {
+    name: String,
+}
+
+#[derive(Clone, Default, MediRessource)]
+struct UserRepository(Arc<Mutex<Vec<String>>>);
+
+impl UserRepository {
+    fn with_users(users: &[&str]) -> Self {
+        UserRepository(Arc::new(Mutex::new(
+            users.iter().map(|user| user.to_string()).collect(),
+        )))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("User not found")]
+struct UserNotFound;
+
+async fn get_user(repo: UserRepository, req: GetUser) -> core::result::Result<String, UserNotFound> {
+    repo.0.lock().unwrap().get(req.id).cloned().ok_or(UserNotFound)
+}
+
+async fn sleep_in_runtime(_req: SleepInRuntime) -> Result<()> {
+    // requires the tokio context, which is not available on the calling thread
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    Ok(())
+}
+
+async fn user_created(repo: UserRepository, evt: UserCreated) -> Result<()> {
+    repo.0.lock().unwrap().push(evt.name);
+    Ok(())
+}
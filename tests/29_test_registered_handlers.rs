@@ -0,0 +1,178 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::StreamExt;
+use medi_rs::{Bus, FromResources, IntoCommand, IntoEvent, IntoStreamCommand, Result, RetryPolicy};
+use medi_rs_macros::{MediCommand, MediEvent, MediRessource, MediStreamCommand, medi_handler};
+
+#[tokio::test]
+async fn add_registered_handlers_should_add_request_handlers() {
+    let bus = Bus::builder()
+        .add_registered_handlers()
+        .append_resources(UserRepository::default())
+        .build()
+        .unwrap();
+
+    let id = bus.send(CreateUser { name: "John".into() }).await.unwrap();
+    let name = bus.send(GetUserName { id }).await.unwrap();
+
+    assert_eq!(name, "John");
+}
+
+#[tokio::test]
+async fn add_registered_handlers_should_add_event_handlers() {
+    let repo = UserRepository::default();
+    let bus = Bus::builder()
+        .add_registered_handlers()
+        .append_resources(repo.clone())
+        .build()
+        .unwrap();
+
+    bus.send(CreateUser { name: "John".into() }).await.unwrap();
+    bus.drain(Some(Duration::from_secs(1))).await;
+
+    let mut audit = repo.audit.lock().unwrap().clone();
+    audit.sort();
+    assert_eq!(audit, ["created John", "welcomed John"]);
+}
+
+#[tokio::test]
+async fn add_registered_handlers_should_add_stream_handlers() {
+    let bus = Bus::builder()
+        .add_registered_handlers()
+        .append_resources(UserRepository::default())
+        .build()
+        .unwrap();
+    bus.send(CreateUser { name: "John".into() }).await.unwrap();
+    bus.send(CreateUser { name: "Jane".into() }).await.unwrap();
+
+    let users: Vec<String> = bus
+        .send_stream(ListUsers)
+        .unwrap()
+        .map(|user| user.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(users, ["John".to_string(), "Jane".to_string()]);
+}
+
+#[tokio::test]
+async fn add_registered_handlers_should_report_handler_which_was_added_before() {
+    let res = Bus::builder()
+        .add_req_handler(create_user_manually)
+        .add_registered_handlers()
+        .append_resources(UserRepository::default())
+        .build();
+
+    let Err(medi_rs::Error::InvalidConfiguration(errors)) = res else {
+        panic!("expected an invalid configuration");
+    };
+    let [medi_rs::Error::DuplicateHandler { request, handlers }] = errors.as_slice() else {
+        panic!("expected a duplicate handler error, got {:?}", errors);
+    };
+    assert!(request.ends_with("CreateUser"));
+    assert_eq!(handlers.len(), 2);
+    assert!(handlers[0].ends_with("create_user_manually"));
+    assert!(handlers[1].ends_with("create_user"));
+}
+
+#[tokio::test]
+async fn add_registered_handlers_should_retry_request_handlers_with_global_policy() {
+    let repo = UserRepository::default();
+    let bus = Bus::builder()
+        .add_registered_handlers()
+        .retry_policy(RetryPolicy::fixed(3, Duration::from_millis(1)))
+        .append_resources(repo.clone())
+        .build()
+        .unwrap();
+
+    let count = bus.send(CountUsers).await.unwrap();
+
+    assert_eq!(count, 0);
+    assert_eq!(*repo.counts.lock().unwrap(), 2);
+}
+
+#[derive(MediCommand)]
+#[medi_command(return_type = usize)]
+struct CreateUser {
+    name: String,
+}
+
+#[derive(MediCommand)]
+#[medi_command(return_type = String)]
+struct GetUserName {
+    id: usize,
+}
+
+#[derive(Clone, MediCommand)]
+#[medi_command(return_type = usize)]
+struct CountUsers;
+
+#[derive(MediStreamCommand)]
+#[medi_stream_command(item_type = String)]
+struct ListUsers;
+
+#[derive(Clone, MediEvent)]
+struct UserCreated {
+    name: String,
+}
+
+#[derive(Clone, Default, MediRessource)]
+struct UserRepository {
+    users: Arc<Mutex<Vec<String>>>,
+    audit: Arc<Mutex<Vec<String>>>,
+    counts: Arc<Mutex<u32>>,
+}
+
+#[medi_handler]
+async fn create_user(repo: UserRepository, bus: Bus, req: CreateUser) -> Result<usize> {
+    let id = {
+        let mut users = repo.users.lock().unwrap();
+        users.push(req.name.clone());
+        users.len() - 1
+    };
+    bus.publish(UserCreated { name: req.name }).await?;
+    Ok(id)
+}
+
+#[medi_handler]
+fn get_user_name(repo: UserRepository, req: GetUserName) -> Result<String> {
+    Ok(repo.users.lock().unwrap()[req.id].clone())
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("User index is not ready")]
+struct IndexNotReady;
+
+/// Fails the first time it is called
+#[medi_handler]
+async fn count_users(repo: UserRepository, _req: CountUsers) -> core::result::Result<usize, IndexNotReady> {
+    let mut counts = repo.counts.lock().unwrap();
+    *counts += 1;
+    if *counts == 1 {
+        return Err(IndexNotReady);
+    }
+    Ok(repo.users.lock().unwrap().len())
+}
+
+#[medi_handler(stream)]
+fn list_users(repo: UserRepository, _req: ListUsers) -> impl futures::Stream<Item = Result<String>> {
+    let users: Vec<Result<String>> = repo.users.lock().unwrap().iter().cloned().map(Ok).collect();
+    futures::stream::iter(users)
+}
+
+#[medi_handler(event)]
+async fn audit_user_created(repo: UserRepository, evt: UserCreated) -> Result<()> {
+    repo.audit.lock().unwrap().push(format!("created {}", evt.name));
+    Ok(())
+}
+
+#[medi_handler(event)]
+fn welcome_user(repo: UserRepository, evt: UserCreated) -> Result<()> {
+    repo.audit.lock().unwrap().push(format!("welcomed {}", evt.name));
+    Ok(())
+}
+
+async fn create_user_manually(_req: CreateUser) -> Result<usize> {
+    Ok(0)
+}
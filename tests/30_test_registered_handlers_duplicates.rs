@@ -0,0 +1,33 @@
+use medi_rs::{Bus, IntoCommand, Result};
+use medi_rs_macros::{MediCommand, medi_handler};
+
+#[tokio::test]
+async fn add_registered_handlers_should_report_duplicate_handlers_on_build() {
+    let res = Bus::builder().add_registered_handlers().build();
+
+    let Err(medi_rs::Error::InvalidConfiguration(errors)) = res else {
+        panic!("expected an invalid configuration");
+    };
+    let [medi_rs::Error::DuplicateHandler { request, handlers }] = errors.as_slice() else {
+        panic!("expected a duplicate handler error, got {:?}", errors);
+    };
+    assert!(request.ends_with("GetUser"));
+    let mut handlers = handlers.clone();
+    handlers.sort();
+    assert!(handlers[0].ends_with("get_user"));
+    assert!(handlers[1].ends_with("get_user_from_cache"));
+}
+
+#[derive(MediCommand)]
+#[medi_command(return_type = String)]
+struct GetUser;
+
+#[medi_handler]
+async fn get_user(_req: GetUser) -> Result<String> {
+    Ok("John".into())
+}
+
+#[medi_handler]
+async fn get_user_from_cache(_req: GetUser) -> Result<String> {
+    Ok("John".into())
+}
@@ -0,0 +1,121 @@
+use futures::StreamExt;
+use medi_rs::{Bus, FromResources, IntoCommand, IntoStreamCommand, Result};
+use medi_rs_macros::{MediCommand, MediRessource, MediStreamCommand};
+
+#[tokio::test]
+async fn build_should_return_error_when_request_handler_is_added_twice() {
+    let res = Bus::builder()
+        .add_req_handler(get_user)
+        .add_req_handler(get_user_from_cache)
+        .build();
+
+    let Err(medi_rs::Error::InvalidConfiguration(errors)) = res else {
+        panic!("expected an invalid configuration");
+    };
+    let [medi_rs::Error::DuplicateHandler { request, handlers }] = errors.as_slice() else {
+        panic!("expected a duplicate handler error, got {:?}", errors);
+    };
+    assert!(request.ends_with("GetUser"));
+    assert!(handlers[0].ends_with("get_user"));
+    assert!(handlers[1].ends_with("get_user_from_cache"));
+}
+
+#[tokio::test]
+async fn build_should_return_error_when_stream_handler_is_added_twice() {
+    let res = Bus::builder()
+        .add_stream_handler(list_users)
+        .add_stream_handler(list_users)
+        .build();
+
+    let Err(medi_rs::Error::InvalidConfiguration(errors)) = res else {
+        panic!("expected an invalid configuration");
+    };
+    assert!(matches!(errors.as_slice(), [medi_rs::Error::DuplicateHandler { .. }]));
+}
+
+#[tokio::test]
+async fn try_add_req_handler_should_return_error_and_keep_builder_when_handler_exists() {
+    let mut builder = Bus::builder();
+    builder.try_add_req_handler(get_user).unwrap();
+
+    let res = builder.try_add_req_handler(get_user_from_cache);
+
+    assert!(matches!(res, Err(medi_rs::Error::DuplicateHandler { .. })));
+    let bus = builder.build().unwrap();
+    assert_eq!(bus.send(GetUser).await.unwrap(), "John");
+}
+
+#[tokio::test]
+async fn try_add_stream_handler_should_return_error_when_handler_exists() {
+    let mut builder = Bus::builder();
+    builder.try_add_stream_handler(list_users).unwrap();
+
+    let res = builder.try_add_stream_handler(list_no_users);
+
+    assert!(matches!(res, Err(medi_rs::Error::DuplicateHandler { .. })));
+    assert!(builder.build().is_ok());
+}
+
+#[tokio::test]
+async fn try_add_blocking_req_handler_should_return_error_when_handler_exists() {
+    let mut builder = Bus::builder();
+    builder.try_add_req_handler(get_user).unwrap();
+
+    let res = builder.try_add_blocking_req_handler(get_user_blocking);
+
+    assert!(matches!(res, Err(medi_rs::Error::DuplicateHandler { .. })));
+}
+
+#[tokio::test]
+async fn allow_handler_override_should_replace_existing_handler() {
+    let bus = Bus::builder()
+        .add_req_handler(get_user_from_database)
+        .add_stream_handler(list_users)
+        .allow_handler_override()
+        .add_req_handler(get_user)
+        .add_stream_handler(list_no_users)
+        .build()
+        .unwrap();
+
+    let user = bus.send(GetUser).await.unwrap();
+    let users: Vec<Result<String>> = bus.send_stream(ListUsers).unwrap().collect().await;
+
+    // the resource of the replaced handler is not required anymore
+    assert_eq!(user, "John");
+    assert!(users.is_empty());
+}
+
+#[derive(MediCommand)]
+#[medi_command(return_type = String)]
+struct GetUser;
+
+#[derive(MediStreamCommand)]
+#[medi_stream_command(item_type = String)]
+struct ListUsers;
+
+#[derive(Clone, MediRessource)]
+struct Database;
+
+async fn get_user(_req: GetUser) -> Result<String> {
+    Ok("John".into())
+}
+
+fn get_user_blocking(_req: GetUser) -> Result<String> {
+    Ok("Blocking John".into())
+}
+
+async fn get_user_from_cache(_req: GetUser) -> Result<String> {
+    Ok("Cached John".into())
+}
+
+async fn get_user_from_database(_db: Database, _req: GetUser) -> Result<String> {
+    Ok("Stored John".into())
+}
+
+fn list_users(_req: ListUsers) -> impl futures::Stream<Item = Result<String>> {
+    futures::stream::iter(vec![Ok("John".to_string())])
+}
+
+fn list_no_users(_req: ListUsers) -> impl futures::Stream<Item = Result<String>> {
+    futures::stream::empty()
+}
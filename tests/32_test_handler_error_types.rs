@@ -0,0 +1,96 @@
+use medi_rs::{Bus, IntoCommand};
+use medi_rs_macros::MediCommand;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[tokio::test]
+async fn send_should_return_boxed_handler_error() {
+    let bus = Bus::builder().add_req_handler(boxed_error_handler).build().unwrap();
+
+    let err = bus.send(GetUser { id: 1 }).await.unwrap_err();
+
+    assert!(matches!(err.get_handler_error::<UserNotFound>(), Some(UserNotFound(1))));
+}
+
+#[tokio::test]
+async fn send_should_return_boxed_message_error() {
+    let bus = Bus::builder().add_req_handler(message_error_handler).build().unwrap();
+
+    let err = bus.send(GetUser { id: 1 }).await.unwrap_err();
+
+    assert_eq!(err.to_string(), "Handler error User 1 is locked");
+}
+
+#[tokio::test]
+async fn downcast_handler_error_should_take_handler_error() {
+    let bus = Bus::builder().add_req_handler(boxed_error_handler).build().unwrap();
+
+    let err = bus.send(GetUser { id: 1 }).await.unwrap_err();
+    let err = err.downcast_handler_error::<std::io::Error>().unwrap_err();
+
+    assert!(matches!(
+        err.downcast_handler_error::<UserNotFound>(),
+        Ok(UserNotFound(1))
+    ));
+}
+
+#[cfg(feature = "anyhow")]
+#[tokio::test]
+async fn send_should_return_anyhow_handler_error() {
+    let bus = Bus::builder().add_req_handler(anyhow_error_handler).build().unwrap();
+
+    let err = bus.send(GetUser { id: 1 }).await.unwrap_err();
+
+    assert!(matches!(err.get_handler_error::<UserNotFound>(), Some(UserNotFound(1))));
+    assert_eq!(err.to_string(), "Handler error loading user 1");
+    assert!(matches!(
+        err.downcast_handler_error::<UserNotFound>(),
+        Ok(UserNotFound(1))
+    ));
+}
+
+#[cfg(feature = "anyhow")]
+#[tokio::test]
+async fn send_should_return_bus_error_of_anyhow_handler_unchanged() {
+    let bus = Bus::builder().add_req_handler(anyhow_nested_handler).build().unwrap();
+
+    let err = bus.send(GetUser { id: 1 }).await.unwrap_err();
+
+    assert!(err.is_handler_not_found());
+}
+
+#[derive(MediCommand)]
+#[medi_command(return_type = String)]
+struct GetUser {
+    id: u32,
+}
+
+#[cfg(feature = "anyhow")]
+#[derive(MediCommand)]
+#[medi_command(return_type = String)]
+struct LoadProfile;
+
+#[derive(Debug, thiserror::Error)]
+#[error("User {0} not found")]
+struct UserNotFound(u32);
+
+async fn boxed_error_handler(req: GetUser) -> Result<String, BoxError> {
+    Err(Box::new(UserNotFound(req.id)))
+}
+
+async fn message_error_handler(req: GetUser) -> Result<String, BoxError> {
+    Err(format!("User {} is locked", req.id).into())
+}
+
+#[cfg(feature = "anyhow")]
+async fn anyhow_error_handler(req: GetUser) -> anyhow::Result<String> {
+    use anyhow::Context;
+
+    Err(UserNotFound(req.id)).context(format!("loading user {}", req.id))
+}
+
+#[cfg(feature = "anyhow")]
+async fn anyhow_nested_handler(bus: Bus, _req: GetUser) -> anyhow::Result<String> {
+    let profile = bus.send(LoadProfile).await?;
+    Ok(profile)
+}